@@ -0,0 +1,126 @@
+use bevy::prelude::*;
+use glam::DVec2;
+
+use super::{
+    object::Planet,
+    player::shared::Player,
+    shared::types::{GameItem, Position, Radius, Velocity},
+};
+
+/// A pass counts once the player comes within this many planet radii.
+const ASSIST_RADIUS_FACTOR: f64 = 5.0;
+/// Turn in the player's heading, in radians, measured across the whole pass, needed to count as an assist.
+const ASSIST_MIN_TURN_RADIANS: f64 = std::f64::consts::FRAC_PI_6;
+const TOAST_SECONDS: f32 = 1.5;
+
+// Components / bundles / resources.
+
+#[derive(Resource, Default)]
+pub struct AssistCounter {
+    pub count: u32,
+}
+
+/// The planet currently being passed, and the player's velocity on the way in.
+#[derive(Resource, Default)]
+pub struct GravityAssistTracker {
+    pub pass: Option<(Entity, Velocity)>,
+}
+
+#[derive(Component)]
+pub struct AssistToast {
+    pub timer: Timer,
+}
+
+// Systems.
+
+pub fn gravity_assist_reset(mut assist_counter: ResMut<AssistCounter>, mut tracker: ResMut<GravityAssistTracker>) {
+    assist_counter.count = 0;
+    tracker.pass = None;
+}
+
+pub fn gravity_assist_check(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    mut assist_counter: ResMut<AssistCounter>,
+    mut tracker: ResMut<GravityAssistTracker>,
+    player_query: Query<(&Position, &Velocity), With<Player>>,
+    planet_query: Query<(Entity, &Position, &Radius), With<Planet>>,
+) {
+    let Ok((player_position, player_velocity)) = player_query.get_single() else {
+        return;
+    };
+
+    let is_near = |position: &Position, radius: &Radius| {
+        let delta_x = player_position.x - position.x;
+        let delta_y = player_position.y - position.y;
+
+        (delta_x * delta_x + delta_y * delta_y).sqrt() <= ASSIST_RADIUS_FACTOR * radius.value
+    };
+
+    match tracker.pass {
+        None => {
+            // Start tracking a pass on entering any planet's neighborhood.
+
+            if let Some((entity, ..)) = planet_query.iter().find(|(_, position, radius)| is_near(position, radius)) {
+                tracker.pass = Some((entity, *player_velocity));
+            }
+        }
+        Some((entity, entry_velocity)) => {
+            let still_near = planet_query
+                .get(entity)
+                .is_ok_and(|(_, position, radius)| is_near(position, radius));
+
+            if still_near {
+                return;
+            }
+
+            // The planets hold still, so a pass trades no speed, in and out at the same distance; what makes it an assist
+            // is how far it bends the player's course.
+
+            tracker.pass = None;
+
+            let entry_heading = DVec2::new(entry_velocity.x.value, entry_velocity.y.value);
+            let exit_heading = DVec2::new(player_velocity.x.value, player_velocity.y.value);
+
+            if entry_heading.angle_between(exit_heading).abs() >= ASSIST_MIN_TURN_RADIANS {
+                assist_counter.count += 1;
+                spawn_assist_toast(&mut commands, &asset_server);
+            }
+        }
+    }
+}
+
+pub fn assist_toast_update(mut commands: Commands, time: Res<Time>, mut query: Query<(Entity, &mut AssistToast)>) {
+    for (entity, mut toast) in query.iter_mut() {
+        if toast.timer.tick(time.delta()).finished() {
+            commands.entity(entity).despawn_recursive();
+        }
+    }
+}
+
+// Helpers.
+
+fn spawn_assist_toast(commands: &mut Commands, asset_server: &Res<AssetServer>) {
+    let toast_text = TextBundle::from_section(
+        "SLINGSHOT!",
+        TextStyle {
+            font_size: 60.0,
+            font: asset_server.load("fonts/HackNerdFontMono-Regular.ttf"),
+            color: Color::YELLOW,
+        },
+    )
+    .with_style(Style {
+        position_type: PositionType::Absolute,
+        top: Val::Percent(40.0),
+        left: Val::Percent(40.0),
+        ..Default::default()
+    });
+
+    commands.spawn((
+        GameItem,
+        AssistToast {
+            timer: Timer::from_seconds(TOAST_SECONDS, TimerMode::Once),
+        },
+        toast_text,
+    ));
+}
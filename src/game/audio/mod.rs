@@ -0,0 +1,67 @@
+use bevy::{audio::Volume, prelude::*};
+use serde::{Deserialize, Serialize};
+
+use super::shared::types::{Action, CollisionEvent, CollisionKind, KeyBindings};
+
+const LAUNCH_SOUND: &str = "audio/scifi/laserLarge_000.ogg";
+const CRASH_SOUND: &str = "audio/scifi/explosionCrunch_000.ogg";
+const SUCCESS_SOUND: &str = "audio/interface/confirmation_001.ogg";
+
+// Resources.
+
+#[derive(Resource, Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct AudioConfig {
+    pub volume: f32,
+    pub muted: bool,
+}
+
+impl Default for AudioConfig {
+    fn default() -> Self {
+        Self { volume: 0.5, muted: false }
+    }
+}
+
+// Systems.
+
+pub fn audio_mute_check(keyboard_input: Res<Input<KeyCode>>, key_bindings: Res<KeyBindings>, mut audio_config: ResMut<AudioConfig>) {
+    if key_bindings.just_pressed(&keyboard_input, Action::ToggleMute) {
+        audio_config.muted = !audio_config.muted;
+    }
+}
+
+pub fn audio_launch(mut commands: Commands, asset_server: Res<AssetServer>, audio_config: Res<AudioConfig>) {
+    play_sound(&mut commands, &asset_server, &audio_config, LAUNCH_SOUND);
+}
+
+pub fn audio_collision(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    audio_config: Res<AudioConfig>,
+    mut collision_events: EventReader<CollisionEvent>,
+) {
+    // Several fixed steps may report the same crash, so only play once per frame.
+
+    if collision_events
+        .iter()
+        .any(|collision_event| matches!(collision_event.kind, CollisionKind::Planet | CollisionKind::Horizon))
+    {
+        play_sound(&mut commands, &asset_server, &audio_config, CRASH_SOUND);
+    }
+}
+
+pub fn audio_success(mut commands: Commands, asset_server: Res<AssetServer>, audio_config: Res<AudioConfig>) {
+    play_sound(&mut commands, &asset_server, &audio_config, SUCCESS_SOUND);
+}
+
+// Helpers.
+
+fn play_sound(commands: &mut Commands, asset_server: &Res<AssetServer>, audio_config: &AudioConfig, path: &'static str) {
+    if audio_config.muted {
+        return;
+    }
+
+    commands.spawn(AudioBundle {
+        source: asset_server.load(path),
+        settings: PlaybackSettings::DESPAWN.with_volume(Volume::new_relative(audio_config.volume)),
+    });
+}
@@ -0,0 +1,38 @@
+use bevy::prelude::*;
+
+use super::shared::{
+    helpers::{length_to_pixel, schwarzschild_radius},
+    types::{GameItem, GravitySource, Mass, PlanetSprite, Position, Radius},
+};
+
+/// The accretion ring is drawn at the innermost stable circular orbit, three Schwarzschild radii out.
+const ACCRETION_RING_FACTOR: f64 = 3.0;
+
+// Components / bundles.
+
+#[derive(Component, Default)]
+pub struct BlackHole;
+
+#[derive(Bundle, Default)]
+pub struct BlackHoleBundle {
+    pub item: GameItem,
+    pub black_hole: BlackHole,
+    pub gravity_source: GravitySource,
+    pub position: Position,
+    pub mass: Mass,
+    pub radius: Radius,
+    pub sprite_type: PlanetSprite,
+    pub sprite: SpriteBundle,
+}
+
+// Systems.
+
+pub fn accretion_ring_update(mut gizmos: Gizmos, query: Query<(&Transform, &Mass), With<BlackHole>>) {
+    for (transform, mass) in query.iter() {
+        let center = transform.translation.truncate();
+        let horizon_px = length_to_pixel(schwarzschild_radius(mass.value)) as f32;
+
+        gizmos.circle_2d(center, horizon_px, Color::rgba(1.0, 1.0, 1.0, 0.3));
+        gizmos.circle_2d(center, ACCRETION_RING_FACTOR as f32 * horizon_px, Color::ORANGE);
+    }
+}
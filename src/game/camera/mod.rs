@@ -0,0 +1,142 @@
+use bevy::prelude::*;
+
+use crate::shared::{SCREEN_HEIGHT_PX, SCREEN_WIDTH_PX};
+
+use super::{
+    destination::Destination,
+    player::shared::Player,
+    shared::types::{Action, KeyBindings},
+};
+
+// Resources.
+
+#[derive(Resource, Clone, Debug, PartialEq)]
+pub struct CameraConfig {
+    pub enabled: bool,
+    /// Fraction of the remaining distance (and zoom) covered each frame.
+    pub follow_lerp: f32,
+    /// Pixels kept clear around the framed entities.
+    pub margin: f32,
+    pub min_zoom: f32,
+    pub max_zoom: f32,
+}
+
+impl Default for CameraConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            follow_lerp: 0.1,
+            margin: 100.0,
+            min_zoom: 1.0,
+            max_zoom: 4.0,
+        }
+    }
+}
+
+// Systems.
+
+pub fn camera_follow_toggle_check(
+    keyboard_input: Res<Input<KeyCode>>,
+    key_bindings: Res<KeyBindings>,
+    mut camera_config: ResMut<CameraConfig>,
+) {
+    if key_bindings.just_pressed(&keyboard_input, Action::ToggleCameraFollow) {
+        camera_config.enabled = !camera_config.enabled;
+    }
+}
+
+pub fn camera_follow_update(
+    camera_config: Res<CameraConfig>,
+    mut camera_query: Query<(&mut Transform, &mut OrthographicProjection), With<Camera>>,
+    player_query: Query<&Transform, (With<Player>, Without<Camera>)>,
+    destination_query: Query<&Transform, (With<Destination>, Without<Camera>)>,
+) {
+    let Ok((mut camera_transform, mut projection)) = camera_query.get_single_mut() else {
+        return;
+    };
+
+    // When disabled, ease back to the fixed, screen-centered view.
+
+    let (target, zoom) = match player_query.get_single() {
+        Ok(player_transform) if camera_config.enabled => {
+            let player = player_transform.translation.truncate();
+
+            // Frame the destination too, if there is exactly one.
+
+            let (min, max) = match destination_query.get_single() {
+                Ok(destination_transform) => {
+                    let destination = destination_transform.translation.truncate();
+
+                    (player.min(destination), player.max(destination))
+                }
+                Err(_) => (player, player),
+            };
+
+            let extent = max - min + Vec2::splat(2.0 * camera_config.margin);
+            let zoom = (extent.x / SCREEN_WIDTH_PX as f32)
+                .max(extent.y / SCREEN_HEIGHT_PX as f32)
+                .clamp(camera_config.min_zoom, camera_config.max_zoom);
+
+            ((min + max) / 2.0, zoom)
+        }
+        _ => (home_translation(), 1.0),
+    };
+
+    let current = camera_transform.translation.truncate();
+    let next = current.lerp(target, camera_config.follow_lerp);
+
+    camera_transform.translation = next.extend(camera_transform.translation.z);
+    projection.scale += (zoom - projection.scale) * camera_config.follow_lerp;
+}
+
+pub fn camera_reset(mut camera_query: Query<(&mut Transform, &mut OrthographicProjection), With<Camera>>) {
+    let Ok((mut camera_transform, mut projection)) = camera_query.get_single_mut() else {
+        return;
+    };
+
+    camera_transform.translation = home_translation().extend(camera_transform.translation.z);
+    projection.scale = 1.0;
+}
+
+// Helpers.
+
+pub fn home_translation() -> Vec2 {
+    Vec2::new(SCREEN_WIDTH_PX as f32, SCREEN_HEIGHT_PX as f32) / 2.0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn camera_follow_moves_toward_the_player() {
+        let mut world = World::new();
+
+        world.insert_resource(CameraConfig { enabled: true, ..Default::default() });
+
+        let camera = world
+            .spawn((
+                Camera::default(),
+                OrthographicProjection::default(),
+                Transform::from_translation(home_translation().extend(0.0)),
+            ))
+            .id();
+        let player = Vec2::new(100.0, 50.0);
+
+        world.spawn((Player, Transform::from_translation(player.extend(0.0))));
+
+        let mut schedule = Schedule::default();
+        schedule.add_systems(camera_follow_update);
+
+        let mut distance = home_translation().distance(player);
+
+        for _ in 0..10 {
+            schedule.run(&mut world);
+
+            let next_distance = world.get::<Transform>(camera).unwrap().translation.truncate().distance(player);
+
+            assert!(next_distance < distance);
+            distance = next_distance;
+        }
+    }
+}
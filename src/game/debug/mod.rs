@@ -0,0 +1,76 @@
+use bevy::prelude::*;
+
+use super::{
+    levels::DevMode,
+    player::shared::Player,
+    shared::{
+        helpers::{get_translation_from_position, verlet_step},
+        types::{Action, GravitySource, InfluenceRadius, KeyBindings, Mass, PhysicsConfig, Position, TimeCompression, Velocity},
+    },
+};
+
+/// How many upcoming fixed steps to dot out.
+const SUBSTEP_DEBUG_FRAMES: usize = 10;
+const SUBSTEP_DEBUG_DOT_PX: f32 = 1.5;
+
+// Systems.
+
+pub fn substep_debug_toggle_check(keyboard_input: Res<Input<KeyCode>>, key_bindings: Res<KeyBindings>, mut dev_mode: ResMut<DevMode>) {
+    if dev_mode.enabled && key_bindings.just_pressed(&keyboard_input, Action::ToggleSubsteps) {
+        dev_mode.substeps = !dev_mode.substeps;
+    }
+}
+
+/// Replays the integrator's substeps for the next few fixed steps, dotting each one, so step size and curvature
+/// are visible; fixed-step boundaries are drawn larger, and atmospheric drag is left out.
+pub fn substep_debug_update(
+    mut gizmos: Gizmos,
+    dev_mode: Res<DevMode>,
+    fixed_time: Res<FixedTime>,
+    time_compression: Res<TimeCompression>,
+    physics_config: Res<PhysicsConfig>,
+    player_query: Query<(Entity, &Position, &Velocity), With<Player>>,
+    masses: Query<(Entity, &Position, &Mass, Option<&InfluenceRadius>), With<GravitySource>>,
+) {
+    if !dev_mode.enabled || !dev_mode.substeps {
+        return;
+    }
+
+    let Ok((player_entity, player_position, player_velocity)) = player_query.get_single() else {
+        return;
+    };
+
+    let time_elapsed = time_compression.time_elapsed(fixed_time.period.as_secs_f64());
+    let substeps = physics_config.substeps(time_elapsed);
+    let substep_time = time_elapsed / substeps as f64;
+
+    let masses: Vec<_> = masses
+        .iter()
+        .map(|(entity, position, mass, influence_radius)| (entity, *position, *mass, influence_radius.copied()))
+        .collect();
+
+    let mut position = *player_position;
+    let mut velocity = *player_velocity;
+
+    for _ in 0..SUBSTEP_DEBUG_FRAMES {
+        for substep in 0..substeps {
+            verlet_step(
+                player_entity,
+                &mut position,
+                &mut velocity,
+                &masses,
+                substep_time,
+                physics_config.softening,
+            );
+
+            let is_frame_end = substep + 1 == substeps;
+            let (radius, color) = if is_frame_end {
+                (2.0 * SUBSTEP_DEBUG_DOT_PX, Color::rgba(1.0, 1.0, 1.0, 0.9))
+            } else {
+                (SUBSTEP_DEBUG_DOT_PX, Color::rgba(0.0, 1.0, 1.0, 0.7))
+            };
+
+            gizmos.circle_2d(get_translation_from_position(&position).truncate(), radius, color);
+        }
+    }
+}
@@ -1,16 +1,52 @@
-use super::shared::types::{GameItem, Mass, PlanetSprite, Position, Radius};
+use super::shared::types::{GameItem, GravitySource, Mass, PlanetSprite, Position, Radius};
 use bevy::prelude::*;
 
-#[derive(Component, Default)]
-pub struct Destination;
+// Components / bundles / resources.
+
+#[derive(Component, Default, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Destination {
+    /// Finishes the level once every waypoint has been visited.
+    #[default]
+    Final,
+    /// Must be visited in ascending `order`, starting at zero.
+    Waypoint { order: u32 },
+}
 
 #[derive(Bundle, Default)]
 pub struct DestinationBundle {
     pub item: GameItem,
     pub destination: Destination,
+    pub gravity_source: GravitySource,
     pub position: Position,
     pub mass: Mass,
     pub radius: Radius,
     pub sprite_type: PlanetSprite,
     pub sprite: SpriteBundle,
 }
+
+#[derive(Resource, Default, Debug)]
+pub struct WaypointProgress {
+    pub next: u32,
+}
+
+impl WaypointProgress {
+    /// Records a visit to `destination`, and returns whether the level is finished.
+    pub fn visit(&mut self, destination: Destination, waypoint_count: u32) -> bool {
+        match destination {
+            Destination::Waypoint { order } => {
+                if order == self.next {
+                    self.next += 1;
+                }
+
+                false
+            }
+            Destination::Final => self.next >= waypoint_count,
+        }
+    }
+}
+
+// Systems.
+
+pub fn waypoint_progress_reset(mut waypoint_progress: ResMut<WaypointProgress>) {
+    waypoint_progress.next = 0;
+}
@@ -0,0 +1,56 @@
+use bevy::prelude::*;
+
+use crate::shared::state::GameState;
+
+use super::shared::types::{GameItem, RunFailed};
+
+// Components.
+
+#[derive(Component, Default)]
+pub struct FailureText;
+
+// Systems.
+
+/// Pauses a failed run, and says why until the next launch.
+pub fn run_failed_update(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    mut run_failed_events: EventReader<RunFailed>,
+    mut game_state: ResMut<NextState<GameState>>,
+    failure_text_query: Query<Entity, With<FailureText>>,
+) {
+    // Several physics ticks can fail in one frame; only the last one is shown.
+
+    let Some(run_failed) = run_failed_events.iter().last() else {
+        return;
+    };
+
+    game_state.set(GameState::Paused);
+
+    for entity in failure_text_query.iter() {
+        commands.entity(entity).despawn_recursive();
+    }
+
+    let failure_text = TextBundle::from_section(
+        format!("failed: {} (R to retry)", run_failed.reason),
+        TextStyle {
+            font_size: 40.0,
+            font: asset_server.load("fonts/HackNerdFontMono-Regular.ttf"),
+            color: Color::RED,
+        },
+    )
+    .with_style(Style {
+        position_type: PositionType::Absolute,
+        top: Val::Percent(40.0),
+        left: Val::Percent(30.0),
+        ..Default::default()
+    });
+
+    commands.spawn((GameItem, FailureText, failure_text));
+}
+
+pub fn failure_text_dismiss(mut commands: Commands, failure_text_query: Query<Entity, With<FailureText>>) {
+    for entity in failure_text_query.iter() {
+        commands.entity(entity).despawn_recursive();
+    }
+}
@@ -0,0 +1,71 @@
+use bevy::prelude::*;
+
+use super::shared::{
+    helpers::{get_translation_from_position, sample_gravitational_field_grid},
+    types::{Action, GravitySource, InfluenceRadius, KeyBindings, Mass, PhysicsConfig, Position, VisualsConfig},
+};
+
+const FIELD_ARROW_COLUMNS: usize = 24;
+const FIELD_ARROW_ROWS: usize = 14;
+const FIELD_ARROW_MAX_LENGTH_PX: f32 = 30.0;
+const FIELD_ARROW_HEAD_PX: f32 = 5.0;
+
+// Systems.
+
+pub fn field_arrows_toggle_check(
+    keyboard_input: Res<Input<KeyCode>>,
+    key_bindings: Res<KeyBindings>,
+    mut visuals_config: ResMut<VisualsConfig>,
+) {
+    if key_bindings.just_pressed(&keyboard_input, Action::ToggleFieldArrows) {
+        visuals_config.field_arrows = !visuals_config.field_arrows;
+    }
+}
+
+pub fn field_arrows_update(
+    mut gizmos: Gizmos,
+    visuals_config: Res<VisualsConfig>,
+    physics_config: Res<PhysicsConfig>,
+    masses: Query<(Entity, &Position, &Mass, Option<&InfluenceRadius>), With<GravitySource>>,
+) {
+    if !visuals_config.field_arrows {
+        return;
+    }
+
+    let masses: Vec<_> = masses
+        .iter()
+        .map(|(entity, position, mass, influence_radius)| (entity, *position, *mass, influence_radius.copied()))
+        .collect();
+    let samples = sample_gravitational_field_grid(FIELD_ARROW_COLUMNS, FIELD_ARROW_ROWS, &masses, physics_config.softening);
+
+    // Scale lengths logarithmically between the weakest and strongest samples, so distant arrows still show.
+
+    let log_magnitudes: Vec<_> = samples
+        .iter()
+        .map(|(_, field)| field.length().max(f64::MIN_POSITIVE).ln())
+        .collect();
+    let min_log = log_magnitudes.iter().copied().fold(f64::INFINITY, f64::min);
+    let max_log = log_magnitudes.iter().copied().fold(f64::NEG_INFINITY, f64::max);
+
+    for ((position, field), log_magnitude) in samples.iter().zip(log_magnitudes) {
+        let direction = field.normalize_or_zero().as_vec2();
+
+        if direction == Vec2::ZERO {
+            continue;
+        }
+
+        let t = if max_log > min_log {
+            ((log_magnitude - min_log) / (max_log - min_log)) as f32
+        } else {
+            1.0
+        };
+
+        let start = get_translation_from_position(position).truncate();
+        let end = start + direction * FIELD_ARROW_MAX_LENGTH_PX * (0.2 + 0.8 * t);
+        let color = Color::rgba(1.0, 1.0 - 0.6 * t, 0.2, 0.6);
+
+        gizmos.line_2d(start, end, color);
+        gizmos.line_2d(end, end - Vec2::from_angle(0.5).rotate(direction) * FIELD_ARROW_HEAD_PX, color);
+        gizmos.line_2d(end, end - Vec2::from_angle(-0.5).rotate(direction) * FIELD_ARROW_HEAD_PX, color);
+    }
+}
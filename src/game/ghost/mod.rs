@@ -0,0 +1,132 @@
+use bevy::{prelude::*, utils::HashMap};
+
+use super::{
+    levels::CurrentLevel,
+    player::{player_sprite::ShipSkin, shared::Player},
+    score::LevelTimer,
+    shared::{
+        helpers::get_translation_from_position,
+        types::{GameItem, Position, Radius, RocketSprite},
+    },
+};
+
+// Components / bundles / resources.
+
+#[derive(Resource, Default)]
+pub struct RunRecording {
+    pub positions: Vec<Position>,
+}
+
+#[derive(Resource, Default)]
+pub struct BestRuns {
+    /// The fastest run's time and recording, per level.
+    pub recordings: HashMap<CurrentLevel, (f64, Vec<Position>)>,
+}
+
+#[derive(Component, Default)]
+pub struct GhostPlayer {
+    pub frame: usize,
+}
+
+#[derive(Bundle, Default)]
+pub struct GhostPlayerBundle {
+    pub item: GameItem,
+    pub ghost: GhostPlayer,
+    pub radius: Radius,
+    pub sprite_type: RocketSprite,
+    pub sprite: SpriteBundle,
+}
+
+// Systems.
+
+#[allow(clippy::too_many_arguments)]
+pub fn ghost_start(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    ship_skin: Res<ShipSkin>,
+    current_level: Res<CurrentLevel>,
+    best_runs: Res<BestRuns>,
+    mut run_recording: ResMut<RunRecording>,
+    ghost_query: Query<Entity, With<GhostPlayer>>,
+    player_query: Query<&Radius, (With<Player>, With<Position>)>,
+) {
+    run_recording.positions.clear();
+
+    for entity in ghost_query.iter() {
+        commands.entity(entity).despawn_recursive();
+    }
+
+    let Some((_, recording)) = best_runs.recordings.get(&*current_level) else {
+        return;
+    };
+
+    let Some(start) = recording.first() else {
+        return;
+    };
+
+    let Ok(player_radius) = player_query.get_single() else {
+        return;
+    };
+
+    commands.spawn(GhostPlayerBundle {
+        radius: Radius { value: player_radius.value },
+        sprite: SpriteBundle {
+            texture: asset_server.load(ship_skin.sprite.as_str()),
+            sprite: Sprite {
+                color: Color::rgba(1.0, 1.0, 1.0, 0.3),
+                ..Default::default()
+            },
+            transform: Transform::from_translation(get_translation_from_position(start)),
+            ..Default::default()
+        },
+        ..Default::default()
+    });
+}
+
+pub fn run_recording_update(mut run_recording: ResMut<RunRecording>, player_query: Query<&Position, With<Player>>) {
+    let Ok(player_position) = player_query.get_single() else {
+        return;
+    };
+
+    run_recording.positions.push(*player_position);
+}
+
+pub fn ghost_update(
+    mut ghost_query: Query<(&mut GhostPlayer, &mut Transform)>,
+    current_level: Res<CurrentLevel>,
+    best_runs: Res<BestRuns>,
+) {
+    let Some((_, recording)) = best_runs.recordings.get(&*current_level) else {
+        return;
+    };
+
+    for (mut ghost, mut transform) in ghost_query.iter_mut() {
+        // Hold at the end of the recording once it has played out.
+
+        let Some(position) = recording.get(ghost.frame).or(recording.last()) else {
+            continue;
+        };
+
+        transform.translation = get_translation_from_position(position);
+        ghost.frame += 1;
+    }
+}
+
+pub fn ghost_finish(
+    level_timer: Res<LevelTimer>,
+    current_level: Res<CurrentLevel>,
+    run_recording: Res<RunRecording>,
+    mut best_runs: ResMut<BestRuns>,
+) {
+    // Only keep the recording if this run beat the best recorded one.
+
+    if let Some((best_seconds, _)) = best_runs.recordings.get(&*current_level) {
+        if level_timer.seconds >= *best_seconds {
+            return;
+        }
+    }
+
+    best_runs
+        .recordings
+        .insert(current_level.clone(), (level_timer.seconds, run_recording.positions.clone()));
+}
@@ -0,0 +1,121 @@
+use bevy::{prelude::*, utils::HashSet};
+use uom::si::f64::Length as UomLength;
+
+use super::shared::{
+    constants::UNIT_RADIUS,
+    helpers::get_translation_from_position,
+    types::{GameItem, GravitySource, Position},
+};
+
+// Components / resources.
+
+#[derive(Resource)]
+pub struct GravityWaveConfig {
+    /// How close two masses must pass to emit a ripple.
+    pub threshold: UomLength,
+    /// Ring expansion in pixels per second.
+    pub speed: f32,
+    pub lifetime_seconds: f32,
+}
+
+impl Default for GravityWaveConfig {
+    fn default() -> Self {
+        Self {
+            threshold: 10.0 * *UNIT_RADIUS,
+            speed: 200.0,
+            lifetime_seconds: 2.0,
+        }
+    }
+}
+
+#[derive(Component)]
+pub struct GravityWave {
+    pub center: Vec2,
+    pub elapsed: f32,
+}
+
+// Helpers.
+
+pub fn ring_radius(elapsed: f32, speed: f32) -> f32 {
+    elapsed.max(0.0) * speed
+}
+
+// Systems.
+
+/// Emits a ripple from the midpoint of any two masses as they close within the threshold.
+pub fn gravity_wave_emit_update(
+    mut commands: Commands,
+    gravity_wave_config: Res<GravityWaveConfig>,
+    masses: Query<(Entity, &Position), With<GravitySource>>,
+    mut close_pairs: Local<HashSet<(Entity, Entity)>>,
+) {
+    let masses: Vec<_> = masses.iter().collect();
+    let mut now_close = HashSet::new();
+
+    for (index, (a_entity, a_position)) in masses.iter().enumerate() {
+        for (b_entity, b_position) in masses.iter().skip(index + 1) {
+            let delta_x = a_position.x - b_position.x;
+            let delta_y = a_position.y - b_position.y;
+
+            if (delta_x * delta_x + delta_y * delta_y).sqrt() > gravity_wave_config.threshold {
+                continue;
+            }
+
+            let pair = (*a_entity.min(b_entity), *a_entity.max(b_entity));
+
+            // Only ripple on the approach, not every frame the pair stays close.
+
+            if !close_pairs.contains(&pair) {
+                let a = get_translation_from_position(a_position).truncate();
+                let b = get_translation_from_position(b_position).truncate();
+
+                commands.spawn((GameItem, GravityWave { center: (a + b) / 2.0, elapsed: 0.0 }));
+            }
+
+            now_close.insert(pair);
+        }
+    }
+
+    *close_pairs = now_close;
+}
+
+pub fn gravity_wave_update(
+    mut commands: Commands,
+    mut gizmos: Gizmos,
+    time: Res<Time>,
+    gravity_wave_config: Res<GravityWaveConfig>,
+    mut wave_query: Query<(Entity, &mut GravityWave)>,
+) {
+    for (entity, mut wave) in wave_query.iter_mut() {
+        wave.elapsed += time.delta_seconds();
+
+        if wave.elapsed >= gravity_wave_config.lifetime_seconds {
+            commands.entity(entity).despawn_recursive();
+            continue;
+        }
+
+        let alpha = 0.6 * (1.0 - wave.elapsed / gravity_wave_config.lifetime_seconds);
+
+        gizmos.circle_2d(
+            wave.center,
+            ring_radius(wave.elapsed, gravity_wave_config.speed),
+            Color::rgba(0.6, 0.8, 1.0, alpha),
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn ring_radius_grows_at_the_wave_speed() {
+        assert_eq!(ring_radius(0.0, 100.0), 0.0);
+        assert_eq!(ring_radius(2.0, 100.0), 200.0);
+    }
+
+    #[test]
+    fn ring_radius_is_zero_before_emission() {
+        assert_eq!(ring_radius(-1.0, 100.0), 0.0);
+    }
+}
@@ -0,0 +1,104 @@
+use bevy::prelude::*;
+
+use crate::shared::{SCREEN_HEIGHT_PX, SCREEN_WIDTH_PX};
+
+use super::shared::{
+    helpers::sample_gravitational_gamma_grid,
+    types::{Action, GameItem, GravitySource, InfluenceRadius, KeyBindings, Mass, PhysicsConfig, Position, VisualsConfig},
+};
+
+const HEATMAP_COLUMNS: usize = 32;
+const HEATMAP_ROWS: usize = 18;
+const HEATMAP_Z: f32 = -0.5;
+
+// Components / bundles.
+
+#[derive(Component, Default)]
+pub struct HeatmapCell {
+    /// Index into the row-major gamma samples.
+    pub index: usize,
+}
+
+#[derive(Bundle, Default)]
+pub struct HeatmapCellBundle {
+    pub item: GameItem,
+    pub cell: HeatmapCell,
+    pub sprite: SpriteBundle,
+}
+
+// Startup systems.
+
+pub fn spawn_heatmap(commands: &mut Commands) {
+    let cell_size = Vec2::new(
+        SCREEN_WIDTH_PX as f32 / HEATMAP_COLUMNS as f32,
+        SCREEN_HEIGHT_PX as f32 / HEATMAP_ROWS as f32,
+    );
+
+    for row in 0..HEATMAP_ROWS {
+        for column in 0..HEATMAP_COLUMNS {
+            let center = cell_size * Vec2::new(column as f32 + 0.5, row as f32 + 0.5);
+
+            commands.spawn(HeatmapCellBundle {
+                cell: HeatmapCell { index: row * HEATMAP_COLUMNS + column },
+                sprite: SpriteBundle {
+                    sprite: Sprite {
+                        custom_size: Some(cell_size),
+                        ..Default::default()
+                    },
+                    transform: Transform::from_translation(center.extend(HEATMAP_Z)),
+                    visibility: Visibility::Hidden,
+                    ..Default::default()
+                },
+                ..Default::default()
+            });
+        }
+    }
+}
+
+// Systems.
+
+pub fn time_dilation_heatmap_toggle_check(
+    keyboard_input: Res<Input<KeyCode>>,
+    key_bindings: Res<KeyBindings>,
+    mut visuals_config: ResMut<VisualsConfig>,
+) {
+    if key_bindings.just_pressed(&keyboard_input, Action::ToggleHeatmap) {
+        visuals_config.time_dilation_heatmap = !visuals_config.time_dilation_heatmap;
+    }
+}
+
+pub fn time_dilation_heatmap_update(
+    visuals_config: Res<VisualsConfig>,
+    mut cell_query: Query<(&mut Sprite, &mut Visibility, &HeatmapCell)>,
+    masses: Query<(Entity, &Position, &Mass, Option<&InfluenceRadius>), With<GravitySource>>,
+    physics_config: Res<PhysicsConfig>,
+) {
+    if !visuals_config.time_dilation_heatmap {
+        for (_, mut visibility, _) in cell_query.iter_mut() {
+            *visibility = Visibility::Hidden;
+        }
+
+        return;
+    }
+
+    let masses: Vec<_> = masses
+        .iter()
+        .map(|(entity, position, mass, influence_radius)| (entity, *position, *mass, influence_radius.copied()))
+        .collect();
+    let gammas = sample_gravitational_gamma_grid(HEATMAP_COLUMNS, HEATMAP_ROWS, &masses, physics_config.softening);
+
+    // Scale logarithmically against the strongest cell, so weak wells still show.
+
+    let max_log_gamma = gammas.iter().copied().fold(1.0, f64::max).ln();
+
+    for (mut sprite, mut visibility, cell) in cell_query.iter_mut() {
+        let t = if max_log_gamma > 0.0 {
+            (gammas[cell.index].ln() / max_log_gamma) as f32
+        } else {
+            0.0
+        };
+
+        sprite.color = Color::rgba(t, 0.0, 1.0 - t, visuals_config.time_dilation_heatmap_opacity);
+        *visibility = Visibility::Visible;
+    }
+}
@@ -0,0 +1,105 @@
+use bevy::prelude::*;
+
+use super::shared::types::{Action, GameItem, KeyBindings};
+
+// Components / bundles / resources.
+
+/// Hints already seen this session.
+#[derive(Resource, Default)]
+pub struct HintsShown {
+    pub launch: bool,
+}
+
+#[derive(Component, Default)]
+pub struct LaunchHint;
+
+#[derive(Component, Default)]
+pub struct HudLegend;
+
+const LEGEND_ENTRIES: [(&str, &str); 6] = [
+    ("t_p", "time on the player's own clock, in days"),
+    ("t_o", "time on a distant observer's clock, in days"),
+    ("Δt", "how far the player's clock has fallen behind"),
+    ("γ_v", "time dilation from the player's speed"),
+    ("γ_g", "time dilation from nearby gravity"),
+    ("E_tot", "energy per kilogram; below zero means bound"),
+];
+
+// Systems.
+
+pub fn launch_hint_update(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    hints_shown: Res<HintsShown>,
+    hint_query: Query<(), With<LaunchHint>>,
+) {
+    if hints_shown.launch || !hint_query.is_empty() {
+        return;
+    }
+
+    let hint_text = TextBundle::from_section(
+        "Click to launch toward the cursor, or aim with the arrows and press Enter",
+        TextStyle {
+            font_size: 30.0,
+            font: asset_server.load("fonts/HackNerdFontMono-Regular.ttf"),
+            color: Color::rgba(1.0, 1.0, 1.0, 0.8),
+        },
+    )
+    .with_style(Style {
+        position_type: PositionType::Absolute,
+        bottom: Val::Px(60.0),
+        left: Val::Px(10.0),
+        ..Default::default()
+    });
+
+    commands.spawn((GameItem, LaunchHint, hint_text));
+}
+
+pub fn launch_hint_dismiss(mut commands: Commands, mut hints_shown: ResMut<HintsShown>, hint_query: Query<Entity, With<LaunchHint>>) {
+    hints_shown.launch = true;
+
+    for entity in hint_query.iter() {
+        commands.entity(entity).despawn_recursive();
+    }
+}
+
+pub fn hud_legend_toggle_check(
+    mut commands: Commands,
+    keyboard_input: Res<Input<KeyCode>>,
+    key_bindings: Res<KeyBindings>,
+    asset_server: Res<AssetServer>,
+    legend_query: Query<Entity, With<HudLegend>>,
+) {
+    if !key_bindings.just_pressed(&keyboard_input, Action::ToggleLegend) {
+        return;
+    }
+
+    if !legend_query.is_empty() {
+        for entity in legend_query.iter() {
+            commands.entity(entity).despawn_recursive();
+        }
+
+        return;
+    }
+
+    let style = TextStyle {
+        font_size: 30.0,
+        font: asset_server.load("fonts/HackNerdFontMono-Regular.ttf"),
+        color: Color::rgba(1.0, 1.0, 1.0, 0.8),
+    };
+
+    let legend_text = TextBundle::from_sections(
+        LEGEND_ENTRIES
+            .iter()
+            .map(|(symbol, description)| TextSection::new(format!("{:>5}  {}\n", symbol, description), style.clone())),
+    )
+    .with_style(Style {
+        position_type: PositionType::Absolute,
+        bottom: Val::Px(100.0),
+        right: Val::Px(10.0),
+        ..Default::default()
+    })
+    .with_background_color(Color::rgba(0.0, 0.0, 0.0, 0.6));
+
+    commands.spawn((GameItem, HudLegend, legend_text));
+}
@@ -0,0 +1,144 @@
+use bevy::prelude::*;
+
+use super::{
+    player::{
+        player_sprite::{KeyboardAim, LaunchConfig},
+        shared::Player,
+    },
+    shared::{
+        helpers::{circular_orbit_velocity_vector, format_velocity, nearest_mass, nudge_to_circular_orbit, unmap_power},
+        types::{Action, GameItem, GravitySource, InfluenceRadius, KeyBindings, Mass, Position, UnitSystem, Velocity},
+    },
+};
+
+// Components / resources.
+
+#[derive(Resource, Default)]
+pub struct OrbitInsertionAssist {
+    pub enabled: bool,
+}
+
+#[derive(Component, Default)]
+pub struct OrbitInsertionText;
+
+// Systems.
+
+pub fn orbit_insertion_toggle_check(
+    keyboard_input: Res<Input<KeyCode>>,
+    key_bindings: Res<KeyBindings>,
+    mut orbit_insertion_assist: ResMut<OrbitInsertionAssist>,
+) {
+    if key_bindings.just_pressed(&keyboard_input, Action::ToggleOrbitInsertion) {
+        orbit_insertion_assist.enabled = !orbit_insertion_assist.enabled;
+    }
+}
+
+/// While enabled, shows the launch speed for a circular orbit about the nearest mass, and snaps the keyboard aim to
+/// that launch while Shift is held.
+#[allow(clippy::too_many_arguments)]
+pub fn orbit_insertion_update(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    keyboard_input: Res<Input<KeyCode>>,
+    key_bindings: Res<KeyBindings>,
+    orbit_insertion_assist: Res<OrbitInsertionAssist>,
+    launch_config: Res<LaunchConfig>,
+    unit_system: Res<UnitSystem>,
+    mut keyboard_aim: ResMut<KeyboardAim>,
+    mut text_query: Query<(Entity, &mut Text), With<OrbitInsertionText>>,
+    player_query: Query<&Position, With<Player>>,
+    masses: Query<(Entity, &Position, &Mass, Option<&InfluenceRadius>), With<GravitySource>>,
+) {
+    if !orbit_insertion_assist.enabled {
+        for (entity, _) in text_query.iter() {
+            commands.entity(entity).despawn_recursive();
+        }
+
+        return;
+    }
+
+    let Ok(player_position) = player_query.get_single() else {
+        return;
+    };
+
+    let masses: Vec<_> = masses
+        .iter()
+        .map(|(entity, position, mass, influence_radius)| (entity, *position, *mass, influence_radius.copied()))
+        .collect();
+
+    let Some((center, mass)) = nearest_mass(player_position, &masses) else {
+        return;
+    };
+
+    let target = circular_orbit_velocity_vector(&center, mass.value, player_position);
+    let target_speed = target.scalar();
+    let power = (target_speed / launch_config.max_velocity()).value;
+
+    let readout = if power <= 1.0 {
+        format!("v_circ = {} (hold Shift to aim)", format_velocity(target_speed, *unit_system))
+    } else {
+        format!("v_circ = {} (beyond the launch cap)", format_velocity(target_speed, *unit_system))
+    };
+
+    match text_query.get_single_mut() {
+        Ok((_, mut text)) => text.sections[0].value = readout,
+        Err(_) => {
+            let text = TextBundle::from_section(
+                readout,
+                TextStyle {
+                    font_size: 30.0,
+                    font: asset_server.load("fonts/HackNerdFontMono-Regular.ttf"),
+                    color: Color::rgba(1.0, 1.0, 1.0, 0.8),
+                },
+            )
+            .with_style(Style {
+                position_type: PositionType::Absolute,
+                bottom: Val::Px(40.0),
+                left: Val::Px(10.0),
+                ..Default::default()
+            });
+
+            commands.spawn((GameItem, OrbitInsertionText, text));
+        }
+    }
+
+    if power <= 1.0 && key_bindings.pressed(&keyboard_input, Action::SnapOrbitInsertion) {
+        keyboard_aim.angle = target.y.value.atan2(target.x.value);
+        keyboard_aim.power = unmap_power(launch_config.power_curve, power);
+    }
+}
+
+/// While the assist is enabled, J snaps a decaying orbit back to circular about the nearest mass.
+#[allow(clippy::type_complexity)]
+pub fn circular_orbit_nudge_check(
+    keyboard_input: Res<Input<KeyCode>>,
+    key_bindings: Res<KeyBindings>,
+    orbit_insertion_assist: Res<OrbitInsertionAssist>,
+    mut player_query: Query<(&Position, &mut Velocity), With<Player>>,
+    masses: Query<(Entity, &Position, &Mass, Option<&InfluenceRadius>), (With<GravitySource>, Without<Player>)>,
+) {
+    if !orbit_insertion_assist.enabled || !key_bindings.just_pressed(&keyboard_input, Action::NudgeOrbit) {
+        return;
+    }
+
+    let Ok((player_position, mut player_velocity)) = player_query.get_single_mut() else {
+        return;
+    };
+
+    let masses: Vec<_> = masses
+        .iter()
+        .map(|(entity, position, mass, influence_radius)| (entity, *position, *mass, influence_radius.copied()))
+        .collect();
+
+    let Some((center, mass)) = nearest_mass(player_position, &masses) else {
+        return;
+    };
+
+    *player_velocity = nudge_to_circular_orbit(&player_velocity, player_position, (&center, mass.value));
+}
+
+pub fn orbit_insertion_text_dismiss(mut commands: Commands, text_query: Query<Entity, With<OrbitInsertionText>>) {
+    for entity in text_query.iter() {
+        commands.entity(entity).despawn_recursive();
+    }
+}
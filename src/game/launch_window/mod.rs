@@ -0,0 +1,89 @@
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::shared::state::GameState;
+
+use super::shared::types::GameItem;
+
+// Components / resources.
+
+/// The span, in real seconds spent aiming, during which a launch is allowed.
+#[derive(Resource, Serialize, Deserialize, Clone, Copy, Debug, PartialEq)]
+pub struct LaunchWindow {
+    pub open_at: f64,
+    pub close_at: f64,
+}
+
+impl LaunchWindow {
+    pub fn is_open(&self, seconds: f64) -> bool {
+        (self.open_at..=self.close_at).contains(&seconds)
+    }
+}
+
+#[derive(Resource, Default)]
+pub struct AimTimer {
+    pub seconds: f64,
+}
+
+#[derive(Component, Default)]
+pub struct LaunchWindowText;
+
+// Helpers.
+
+/// Whether a launch fired now should go through; levels without a window always allow it.
+pub fn is_launch_allowed(launch_window: Option<&LaunchWindow>, aim_timer: &AimTimer) -> bool {
+    launch_window.is_none_or(|launch_window| launch_window.is_open(aim_timer.seconds))
+}
+
+// Startup systems.
+
+pub fn spawn_launch_window_text(commands: &mut Commands, asset_server: &Res<AssetServer>) {
+    let text = TextBundle::from_section(
+        "",
+        TextStyle {
+            font_size: 30.0,
+            font: asset_server.load("fonts/HackNerdFontMono-Regular.ttf"),
+            ..Default::default()
+        },
+    )
+    .with_style(Style {
+        position_type: PositionType::Absolute,
+        bottom: Val::Px(10.0),
+        left: Val::Px(10.0),
+        ..Default::default()
+    });
+
+    commands.spawn((GameItem, LaunchWindowText, text));
+}
+
+// Systems.
+
+pub fn aim_timer_update(mut aim_timer: ResMut<AimTimer>, time: Res<Time>) {
+    aim_timer.seconds += time.delta_seconds() as f64;
+}
+
+pub fn launch_window_text_update(
+    mut query: Query<&mut Text, With<LaunchWindowText>>,
+    launch_window: Option<Res<LaunchWindow>>,
+    aim_timer: Res<AimTimer>,
+    game_state: Res<State<GameState>>,
+) {
+    let Ok(mut text) = query.get_single_mut() else {
+        return;
+    };
+
+    let seconds = aim_timer.seconds;
+
+    text.sections[0].value = match launch_window {
+        Some(launch_window) if *game_state.get() == GameState::Paused => {
+            if seconds < launch_window.open_at {
+                format!("window opens in {:.1}s", launch_window.open_at - seconds)
+            } else if seconds <= launch_window.close_at {
+                format!("window open: {:.1}s left", launch_window.close_at - seconds)
+            } else {
+                "window closed (R to retry)".to_string()
+            }
+        }
+        _ => String::new(),
+    };
+}
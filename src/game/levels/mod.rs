@@ -1,30 +1,187 @@
+pub mod spec;
+
+use std::path::Path;
+
 use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::shared::state::{AppState, GameState};
 
+use self::spec::{load_level_spec, spawn_level_from_spec};
 use super::{
     destination::DestinationBundle,
+    heatmap::spawn_heatmap,
+    launch_window::{spawn_launch_window_text, AimTimer, LaunchWindow},
+    minimap::spawn_minimap,
     object::StaticPlanetBundle,
-    observer::spawn_observer_clock,
-    player::{player_clock::spawn_player_clock, player_sprite::PlayerSpriteBundle},
+    observer::{spawn_observer_clock, ObserverTimeLimit},
+    outcome::spawn_launch_outcome_text,
+    player::{
+        player_clock::spawn_player_clock,
+        player_sprite::{LaunchConfig, PlayerSpriteBundle, ShipSkin},
+    },
     shared::{
         constants::{MASS_OF_EARTH, MASS_OF_SUN, UNIT_RADIUS},
         helpers::get_position_from_percentage,
-        types::{GameItem, Mass, Radius},
+        types::{Action, GameItem, GameplayScale, KeyBindings, Radius, SimRng, SimSeed, TimeCompression, WorldBounds},
     },
 };
 
+const LEVEL_FADE_SECONDS: f32 = 0.5;
+
 // Components / bundles / resources.
 
-#[derive(Resource, Default)]
+#[derive(Resource, Serialize, Deserialize, Default, Debug, Clone, PartialEq, Eq, Hash)]
 pub enum CurrentLevel {
     #[default]
     One,
+    Slingshot,
+    Custom(String),
+}
+
+impl CurrentLevel {
+    pub fn label(&self) -> String {
+        match self {
+            CurrentLevel::One => "Level 1".to_string(),
+            CurrentLevel::Slingshot => "Slingshot".to_string(),
+            CurrentLevel::Custom(name) => name.clone(),
+        }
+    }
+
+    pub fn first() -> CurrentLevel {
+        CurrentLevel::One
+    }
+
+    /// The level after this one in `LevelList` order, or `None` if this is the last.
+    pub fn next(&self, level_list: &LevelList) -> Option<CurrentLevel> {
+        let index = level_list.levels.iter().position(|level| level == self)?;
+
+        level_list.levels.get(index + 1).cloned()
+    }
+
+    /// The level before this one in `LevelList` order, or `None` if this is the first.
+    pub fn previous(&self, level_list: &LevelList) -> Option<CurrentLevel> {
+        let index = level_list.levels.iter().position(|level| level == self)?;
+
+        level_list.levels.get(index.checked_sub(1)?).cloned()
+    }
+}
+
+/// Enables playtesting shortcuts, like cycling levels with `[` and `]`; on by default in debug builds.
+#[derive(Resource)]
+pub struct DevMode {
+    pub enabled: bool,
+    /// Shows each mass's share of the player's gamma; toggled with F3.
+    pub gamma_breakdown: bool,
+    /// Dots the player's upcoming physics substeps; toggled with F4.
+    pub substeps: bool,
+}
+
+impl Default for DevMode {
+    fn default() -> Self {
+        Self {
+            enabled: cfg!(debug_assertions),
+            gamma_breakdown: false,
+            substeps: false,
+        }
+    }
+}
+
+/// The per-level tuning applied by `spawn_level_common`; the built-in levels use the defaults.
+#[derive(Default)]
+pub struct LevelSettings {
+    pub time_compression: TimeCompression,
+    pub launch_config: LaunchConfig,
+    pub launch_window: Option<LaunchWindow>,
+    pub observer_time_limit: Option<ObserverTimeLimit>,
+    pub world_bounds: WorldBounds,
+}
+
+#[derive(Component)]
+pub struct LevelFade {
+    pub timer: Timer,
+}
+
+/// Every playable level, in order; scanned once at startup, so the disk isn't read again each time it's needed.
+#[derive(Resource, Clone, Debug, PartialEq)]
+pub struct LevelList {
+    pub levels: Vec<CurrentLevel>,
+}
+
+impl LevelList {
+    /// The built-in levels, followed by the named RON levels, sorted by name.
+    pub fn new(custom_levels: impl IntoIterator<Item = String>) -> Self {
+        let mut custom_levels: Vec<_> = custom_levels.into_iter().collect();
+
+        custom_levels.sort();
+
+        let levels = [CurrentLevel::first(), CurrentLevel::Slingshot]
+            .into_iter()
+            .chain(custom_levels.into_iter().map(CurrentLevel::Custom))
+            .collect();
+
+        Self { levels }
+    }
+}
+
+impl FromWorld for LevelList {
+    fn from_world(world: &mut World) -> Self {
+        // Ask the asset server, so levels are found relative to the assets folder rather than the working directory;
+        // where it can't list directories (e.g., on the web), only the built-in levels are offered.
+
+        let custom_levels = world
+            .get_resource::<AssetServer>()
+            .and_then(|asset_server| asset_server.asset_io().read_directory(Path::new("levels")).ok())
+            .into_iter()
+            .flatten()
+            .filter(|path| path.extension().is_some_and(|extension| extension == "ron"))
+            .filter_map(|path| path.file_stem().map(|stem| stem.to_string_lossy().into_owned()));
+
+        Self::new(custom_levels)
+    }
+}
+
+// Helpers.
+
+fn reseed_sim_rng(world: &mut World) {
+    let seed = world.get_resource::<SimSeed>().copied().unwrap_or_default();
+
+    world.insert_resource(SimRng::new(seed));
 }
 
 // Startup systems.
 
-pub fn spawn_level(commands: Commands, asset_server: Res<AssetServer>, current_level: Res<CurrentLevel>) {
-    match current_level.into_inner() {
-        CurrentLevel::One => level1(commands, asset_server),
+pub fn spawn_level(
+    commands: Commands,
+    asset_server: Res<AssetServer>,
+    ship_skin: Res<ShipSkin>,
+    gameplay_scale: Res<GameplayScale>,
+    current_level: Res<CurrentLevel>,
+) {
+    spawn_specific_level(commands, asset_server, &ship_skin, &gameplay_scale, &current_level);
+}
+
+pub fn spawn_specific_level(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    ship_skin: &ShipSkin,
+    gameplay_scale: &GameplayScale,
+    level: &CurrentLevel,
+) {
+    commands.add(reseed_sim_rng);
+
+    match level {
+        CurrentLevel::One => level1(commands, asset_server, ship_skin, gameplay_scale),
+        CurrentLevel::Slingshot => level_slingshot(commands, asset_server, ship_skin, gameplay_scale),
+        CurrentLevel::Custom(name) => match load_level_spec(&asset_server, name) {
+            Ok(spec) => spawn_level_from_spec(commands, asset_server, ship_skin, gameplay_scale, &spec),
+            Err(e) => {
+                // Fall back to the first level, rather than leave the player with nothing to play.
+
+                println!("level `{}` failed to load: {}", name, e);
+                level1(commands, asset_server, ship_skin, gameplay_scale);
+            }
+        },
     }
 }
 
@@ -34,13 +191,202 @@ pub fn despawn_level(mut commands: Commands, query: Query<Entity, With<GameItem>
     }
 }
 
+/// Clears out the current level and spawns `level` in its place, ready to aim.
+pub fn respawn_level(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    ship_skin: &ShipSkin,
+    gameplay_scale: &GameplayScale,
+    game_items: &Query<Entity, With<GameItem>>,
+    game_state: &mut NextState<GameState>,
+    level: &CurrentLevel,
+) {
+    for entity in game_items.iter() {
+        commands.entity(entity).despawn_recursive();
+    }
+
+    game_state.set(GameState::Paused);
+
+    spawn_specific_level(commands, asset_server, ship_skin, gameplay_scale, level);
+}
+
+// Restart button.
+
+#[allow(clippy::too_many_arguments)]
+pub fn restart_level_check(
+    keyboard_input: Res<Input<KeyCode>>,
+    key_bindings: Res<KeyBindings>,
+    commands: Commands,
+    query: Query<Entity, With<GameItem>>,
+    asset_server: Res<AssetServer>,
+    ship_skin: Res<ShipSkin>,
+    gameplay_scale: Res<GameplayScale>,
+    current_level: Res<CurrentLevel>,
+    mut game_state: ResMut<NextState<GameState>>,
+) {
+    if !key_bindings.just_pressed(&keyboard_input, Action::Restart) {
+        return;
+    }
+
+    respawn_level(
+        commands,
+        asset_server,
+        &ship_skin,
+        &gameplay_scale,
+        &query,
+        &mut game_state,
+        &current_level,
+    );
+}
+
+// Next level button.
+
+#[allow(clippy::too_many_arguments)]
+pub fn next_level_check(
+    keyboard_input: Res<Input<KeyCode>>,
+    key_bindings: Res<KeyBindings>,
+    commands: Commands,
+    query: Query<Entity, With<GameItem>>,
+    asset_server: Res<AssetServer>,
+    ship_skin: Res<ShipSkin>,
+    gameplay_scale: Res<GameplayScale>,
+    mut current_level: ResMut<CurrentLevel>,
+    level_list: Res<LevelList>,
+    mut game_state: ResMut<NextState<GameState>>,
+    mut app_state: ResMut<NextState<AppState>>,
+) {
+    if !key_bindings.just_pressed(&keyboard_input, Action::NextLevel) {
+        return;
+    }
+
+    // After the last level, head back to the menu instead.
+
+    let Some(next_level) = current_level.next(&level_list) else {
+        game_state.set(GameState::Paused);
+        app_state.set(AppState::Menu);
+        return;
+    };
+
+    respawn_level(
+        commands,
+        asset_server,
+        &ship_skin,
+        &gameplay_scale,
+        &query,
+        &mut game_state,
+        &next_level,
+    );
+
+    *current_level = next_level;
+}
+
+// Level cycle buttons.
+
+#[allow(clippy::too_many_arguments)]
+pub fn level_cycle_check(
+    keyboard_input: Res<Input<KeyCode>>,
+    key_bindings: Res<KeyBindings>,
+    dev_mode: Res<DevMode>,
+    mut commands: Commands,
+    query: Query<Entity, With<GameItem>>,
+    asset_server: Res<AssetServer>,
+    ship_skin: Res<ShipSkin>,
+    gameplay_scale: Res<GameplayScale>,
+    mut current_level: ResMut<CurrentLevel>,
+    level_list: Res<LevelList>,
+    mut game_state: ResMut<NextState<GameState>>,
+) {
+    if !dev_mode.enabled {
+        return;
+    }
+
+    let level = if key_bindings.just_pressed(&keyboard_input, Action::CycleNextLevel) {
+        current_level.next(&level_list)
+    } else if key_bindings.just_pressed(&keyboard_input, Action::CyclePreviousLevel) {
+        current_level.previous(&level_list)
+    } else {
+        return;
+    };
+
+    let Some(level) = level else {
+        return;
+    };
+
+    // Fade in from black, so the jump doesn't look like a glitch; it isn't in `query` yet, so survives the respawn.
+
+    commands.spawn((
+        GameItem,
+        LevelFade {
+            timer: Timer::from_seconds(LEVEL_FADE_SECONDS, TimerMode::Once),
+        },
+        NodeBundle {
+            style: Style {
+                position_type: PositionType::Absolute,
+                width: Val::Percent(100.0),
+                height: Val::Percent(100.0),
+                ..Default::default()
+            },
+            background_color: Color::BLACK.into(),
+            z_index: ZIndex::Global(20),
+            ..Default::default()
+        },
+    ));
+
+    respawn_level(commands, asset_server, &ship_skin, &gameplay_scale, &query, &mut game_state, &level);
+
+    *current_level = level;
+}
+
+pub fn level_fade_update(mut commands: Commands, time: Res<Time>, mut fade_query: Query<(Entity, &mut LevelFade, &mut BackgroundColor)>) {
+    for (entity, mut fade, mut background_color) in fade_query.iter_mut() {
+        fade.timer.tick(time.delta());
+
+        if fade.timer.finished() {
+            commands.entity(entity).despawn_recursive();
+            continue;
+        }
+
+        background_color.0.set_a(fade.timer.percent_left());
+    }
+}
+
 // Levels.
 
-pub fn level1(mut commands: Commands, asset_server: Res<AssetServer>) {
+/// Sets the tuning resources and spawns the HUD that every level shares.
+pub fn spawn_level_common(commands: &mut Commands, asset_server: &Res<AssetServer>, settings: LevelSettings) {
+    commands.insert_resource(settings.time_compression);
+    commands.insert_resource(settings.launch_config);
+    commands.insert_resource(AimTimer::default());
+    commands.insert_resource(settings.world_bounds);
+
+    match settings.launch_window {
+        Some(launch_window) => commands.insert_resource(launch_window),
+        None => commands.remove_resource::<LaunchWindow>(),
+    }
+
+    match settings.observer_time_limit {
+        Some(observer_time_limit) => commands.insert_resource(observer_time_limit),
+        None => commands.remove_resource::<ObserverTimeLimit>(),
+    }
+
     // Spawn clocks.
 
-    spawn_player_clock(&mut commands, &asset_server);
-    spawn_observer_clock(&mut commands, &asset_server);
+    spawn_player_clock(commands, asset_server);
+    spawn_observer_clock(commands, asset_server);
+    spawn_launch_window_text(commands, asset_server);
+    spawn_launch_outcome_text(commands, asset_server);
+
+    // Spawn minimap.
+
+    spawn_minimap(commands);
+
+    // Spawn heatmap.
+
+    spawn_heatmap(commands);
+}
+
+pub fn level1(mut commands: Commands, asset_server: Res<AssetServer>, ship_skin: &ShipSkin, gameplay_scale: &GameplayScale) {
+    spawn_level_common(&mut commands, &asset_server, LevelSettings::default());
 
     // Spawn player.
 
@@ -48,7 +394,7 @@ pub fn level1(mut commands: Commands, asset_server: Res<AssetServer>) {
         position: get_position_from_percentage(0.3, 0.3),
         radius: Radius { value: *UNIT_RADIUS / 4.0 },
         sprite: SpriteBundle {
-            texture: asset_server.load("sprites/space/Rockets/spaceRockets_003.png"),
+            texture: asset_server.load(ship_skin.sprite.as_str()),
             ..Default::default()
         },
         ..Default::default()
@@ -60,7 +406,7 @@ pub fn level1(mut commands: Commands, asset_server: Res<AssetServer>) {
     commands.spawn(StaticPlanetBundle {
         position: get_position_from_percentage(0.5, 0.5),
         radius: Radius { value: 3.0 * *UNIT_RADIUS },
-        mass: Mass { value: *MASS_OF_SUN },
+        mass: gameplay_scale.mass(*MASS_OF_SUN),
         sprite: SpriteBundle {
             texture: asset_server.load("sprites/planets/planet04.png"),
             ..Default::default()
@@ -72,7 +418,7 @@ pub fn level1(mut commands: Commands, asset_server: Res<AssetServer>) {
     commands.spawn(StaticPlanetBundle {
         position: get_position_from_percentage(0.8, 0.7),
         radius: Radius { value: 2.0 * *UNIT_RADIUS },
-        mass: Mass { value: 0.4 * *MASS_OF_SUN },
+        mass: gameplay_scale.mass(0.4 * *MASS_OF_SUN),
         sprite: SpriteBundle {
             texture: asset_server.load("sprites/planets/planet05.png"),
             ..Default::default()
@@ -84,7 +430,7 @@ pub fn level1(mut commands: Commands, asset_server: Res<AssetServer>) {
     commands.spawn(StaticPlanetBundle {
         position: get_position_from_percentage(0.28, 0.28),
         radius: Radius { value: 2.0 * *UNIT_RADIUS },
-        mass: Mass { value: *MASS_OF_EARTH },
+        mass: gameplay_scale.mass(*MASS_OF_EARTH),
         sprite: SpriteBundle {
             texture: asset_server.load("sprites/planets/planet03.png"),
             ..Default::default()
@@ -97,7 +443,7 @@ pub fn level1(mut commands: Commands, asset_server: Res<AssetServer>) {
     commands.spawn(DestinationBundle {
         position: get_position_from_percentage(0.9, 0.9),
         radius: Radius { value: 4.0 * *UNIT_RADIUS },
-        mass: Mass { value: 0.6 * *MASS_OF_SUN },
+        mass: gameplay_scale.mass(0.6 * *MASS_OF_SUN),
         sprite: SpriteBundle {
             texture: asset_server.load("sprites/planets/noise00.png"),
             ..Default::default()
@@ -105,3 +451,158 @@ pub fn level1(mut commands: Commands, asset_server: Res<AssetServer>) {
         ..Default::default()
     });
 }
+
+pub fn level_slingshot(mut commands: Commands, asset_server: Res<AssetServer>, ship_skin: &ShipSkin, gameplay_scale: &GameplayScale) {
+    spawn_level_common(&mut commands, &asset_server, LevelSettings::default());
+
+    // Spawn player.
+
+    commands.spawn(PlayerSpriteBundle {
+        position: get_position_from_percentage(0.1, 0.5),
+        radius: Radius { value: *UNIT_RADIUS / 4.0 },
+        sprite: SpriteBundle {
+            texture: asset_server.load(ship_skin.sprite.as_str()),
+            ..Default::default()
+        },
+        ..Default::default()
+    });
+
+    // Spawn objects.
+
+    // SUN, squarely between the start and the destination, so a straight shot hits it.
+    commands.spawn(StaticPlanetBundle {
+        position: get_position_from_percentage(0.5, 0.5),
+        radius: Radius { value: 5.0 * *UNIT_RADIUS },
+        mass: gameplay_scale.mass(2.0 * *MASS_OF_SUN),
+        sprite: SpriteBundle {
+            texture: asset_server.load("sprites/planets/planet04.png"),
+            ..Default::default()
+        },
+        ..Default::default()
+    });
+
+    // Spawn destination.
+
+    commands.spawn(DestinationBundle {
+        position: get_position_from_percentage(0.9, 0.5),
+        radius: Radius { value: 3.0 * *UNIT_RADIUS },
+        mass: gameplay_scale.mass(0.1 * *MASS_OF_SUN),
+        sprite: SpriteBundle {
+            texture: asset_server.load("sprites/planets/noise00.png"),
+            ..Default::default()
+        },
+        ..Default::default()
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game::{
+        player::{player_sprite::SHIP_SKINS, shared::Player},
+        shared::types::RocketSprite,
+    };
+
+    #[test]
+    fn next_chains_through_every_level() {
+        let level_list = LevelList::new(["b".to_string(), "a".to_string()]);
+
+        let mut chain = vec![CurrentLevel::first()];
+
+        while let Some(next) = chain.last().unwrap().next(&level_list) {
+            chain.push(next);
+        }
+
+        assert_eq!(
+            chain,
+            vec![
+                CurrentLevel::One,
+                CurrentLevel::Slingshot,
+                CurrentLevel::Custom("a".to_string()),
+                CurrentLevel::Custom("b".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn last_level_has_no_next() {
+        let level_list = LevelList::new([]);
+
+        assert_eq!(CurrentLevel::Slingshot.next(&level_list), None);
+        assert_eq!(CurrentLevel::Custom("missing".to_string()).next(&level_list), None);
+    }
+
+    #[test]
+    fn previous_walks_the_chain_backward() {
+        let level_list = LevelList::new(["a".to_string()]);
+
+        assert_eq!(
+            CurrentLevel::Custom("a".to_string()).previous(&level_list),
+            Some(CurrentLevel::Slingshot)
+        );
+        assert_eq!(CurrentLevel::Slingshot.previous(&level_list), Some(CurrentLevel::One));
+        assert_eq!(CurrentLevel::One.previous(&level_list), None);
+    }
+
+    #[test]
+    fn rebound_restart_fires_on_the_new_key_only() {
+        let mut app = App::new();
+
+        app.add_plugins((MinimalPlugins, AssetPlugin::default()))
+            .add_state::<GameState>()
+            .init_resource::<Input<KeyCode>>()
+            .init_resource::<KeyBindings>()
+            .init_resource::<ShipSkin>()
+            .init_resource::<GameplayScale>()
+            .init_resource::<CurrentLevel>()
+            .add_systems(Update, restart_level_check);
+
+        app.world.resource_mut::<KeyBindings>().bind(Action::Restart, [KeyCode::T]);
+        app.world.resource_mut::<NextState<GameState>>().set(GameState::Running);
+        app.update();
+
+        let marker = app.world.spawn(GameItem).id();
+
+        // The old key no longer restarts the level.
+
+        app.world.resource_mut::<Input<KeyCode>>().press(KeyCode::R);
+        app.update();
+        app.world.resource_mut::<Input<KeyCode>>().clear();
+        app.update();
+
+        assert!(app.world.get_entity(marker).is_some());
+        assert_eq!(*app.world.resource::<State<GameState>>().get(), GameState::Running);
+
+        // The new one does, clearing out the level and waiting for the next launch.
+
+        app.world.resource_mut::<Input<KeyCode>>().press(KeyCode::T);
+        app.update();
+        app.world.resource_mut::<Input<KeyCode>>().clear();
+        app.update();
+
+        assert!(app.world.get_entity(marker).is_none());
+        assert_eq!(*app.world.resource::<State<GameState>>().get(), GameState::Paused);
+    }
+
+    #[test]
+    fn ship_skin_sets_the_player_sprite() {
+        for level in [CurrentLevel::One, CurrentLevel::Slingshot] {
+            let mut app = App::new();
+
+            app.add_plugins((MinimalPlugins, AssetPlugin::default()))
+                .insert_resource(ShipSkin { sprite: SHIP_SKINS[0].to_string() })
+                .init_resource::<GameplayScale>()
+                .insert_resource(level.clone())
+                .add_systems(Startup, spawn_level);
+            app.update();
+
+            let texture = app
+                .world
+                .query_filtered::<&Handle<Image>, (With<Player>, With<RocketSprite>)>()
+                .single(&app.world);
+            let path = app.world.resource::<AssetServer>().get_handle_path(texture).unwrap();
+
+            assert_eq!(path.path(), Path::new(SHIP_SKINS[0]), "{:?}", level);
+        }
+    }
+}
@@ -0,0 +1,340 @@
+use std::{io, path::Path};
+
+use bevy::{prelude::*, tasks::IoTaskPool};
+use serde::{Deserialize, Serialize};
+use uom::si::{
+    f64::{Time as UomTime, Velocity as UomVelocity},
+    time::day,
+    velocity::kilometer_per_second,
+};
+
+use super::{spawn_level_common, LevelSettings};
+use crate::game::{
+    black_hole::BlackHoleBundle,
+    destination::{Destination, DestinationBundle},
+    launch_window::LaunchWindow,
+    object::StaticPlanetBundle,
+    observer::ObserverTimeLimit,
+    player::player_sprite::{LaunchConfig, PlayerSpriteBundle, ShipSkin},
+    shared::{
+        constants::{MASS_OF_SUN, UNIT_RADIUS},
+        helpers::get_position_from_percentage,
+        types::{
+            BoundaryMode, CaptureRadius, CollisionResponse, GameplayScale, PowerCurve, Radius, TimeCompression, Velocity, VisualRadius,
+            WorldBounds,
+        },
+    },
+};
+
+// Specs.
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct LevelSpec {
+    pub player: PlayerSpec,
+    pub bodies: Vec<BodySpec>,
+    /// Black holes, whose event horizons are always fatal.
+    #[serde(default)]
+    pub black_holes: Vec<BodySpec>,
+    /// Waypoints to visit, in order, before the destination.
+    #[serde(default)]
+    pub waypoints: Vec<BodySpec>,
+    pub destination: BodySpec,
+    /// Simulated days per real second; defaults to `DAYS_PER_SECOND`.
+    #[serde(default)]
+    pub time_compression: Option<f64>,
+    /// Factor inflating every mass; defaults to the `GameplayScale` resource.
+    #[serde(default)]
+    pub mass_factor: Option<f64>,
+    /// Launch speed at full power, as a fraction of c; defaults to `MAX_LAUNCH_VELOCITY_FRACTION_OF_C`.
+    #[serde(default)]
+    pub max_launch_velocity_fraction: Option<f64>,
+    #[serde(default)]
+    pub power_curve: PowerCurve,
+    /// When, in seconds spent aiming, launching is allowed; defaults to always.
+    #[serde(default)]
+    pub launch_window: Option<LaunchWindow>,
+    /// Observer days by which the player must arrive; defaults to no limit.
+    #[serde(default)]
+    pub observer_time_limit: Option<f64>,
+    /// What happens at the screen edges; defaults to nothing.
+    #[serde(default)]
+    pub boundary_mode: BoundaryMode,
+}
+
+/// The player's starting state; the sprite is always the player's `ShipSkin`.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct PlayerSpec {
+    /// Position as a fraction of the screen.
+    pub position: (f64, f64),
+    /// Initial velocity in km/s.
+    #[serde(default)]
+    pub velocity: (f64, f64),
+    /// Radius in multiples of `UNIT_RADIUS`.
+    pub radius: f64,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct BodySpec {
+    /// Position as a fraction of the screen.
+    pub position: (f64, f64),
+    /// Radius in multiples of `UNIT_RADIUS`.
+    pub radius: f64,
+    /// Mass in multiples of `MASS_OF_SUN`, before the `mass_factor` scaling.
+    pub mass: f64,
+    pub sprite: String,
+    /// What touching the body does; only applies to `bodies`.
+    #[serde(default)]
+    pub collision: CollisionResponse,
+    /// Drawn radius in multiples of `UNIT_RADIUS`, if different from `radius`; only applies to `bodies`.
+    #[serde(default)]
+    pub visual_radius: Option<f64>,
+    /// Capture radius in multiples of `UNIT_RADIUS`, if different from `radius`; only applies to the destination and
+    /// waypoints.
+    #[serde(default)]
+    pub capture_radius: Option<f64>,
+}
+
+// Loading.
+
+pub fn load_level_spec(asset_server: &AssetServer, name: &str) -> io::Result<LevelSpec> {
+    let path = Path::new("levels").join(format!("{}.ron", name));
+    let asset_io = asset_server.asset_io();
+
+    // Read through the asset server, so levels load from the assets folder wherever the game is started from.
+
+    let contents = IoTaskPool::get()
+        .scope(|scope| scope.spawn(asset_io.load_path(&path)))
+        .pop()
+        .ok_or_else(|| io::Error::from(io::ErrorKind::Interrupted))?
+        .map_err(|e| io::Error::new(io::ErrorKind::NotFound, e))?;
+
+    ron::de::from_bytes(&contents).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+}
+
+// Spawning.
+
+pub fn spawn_level_from_spec(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    ship_skin: &ShipSkin,
+    gameplay_scale: &GameplayScale,
+    spec: &LevelSpec,
+) {
+    // The spec's mass factor only applies to its own bodies, so it doesn't carry over into the next level.
+
+    let gameplay_scale = spec
+        .mass_factor
+        .map_or(*gameplay_scale, |mass_factor| GameplayScale { mass_factor });
+
+    // Set the time compression, launch cap, launch window, time limit, and bounds.
+
+    spawn_level_common(
+        &mut commands,
+        &asset_server,
+        LevelSettings {
+            time_compression: spec.time_compression.map(TimeCompression).unwrap_or_default(),
+            launch_config: LaunchConfig {
+                max_velocity_fraction: spec
+                    .max_launch_velocity_fraction
+                    .unwrap_or(LaunchConfig::default().max_velocity_fraction),
+                power_curve: spec.power_curve,
+            },
+            launch_window: spec.launch_window,
+            observer_time_limit: spec.observer_time_limit.map(|days| ObserverTimeLimit(UomTime::new::<day>(days))),
+            world_bounds: WorldBounds {
+                mode: spec.boundary_mode,
+                ..Default::default()
+            },
+        },
+    );
+
+    // Spawn player.
+
+    commands.spawn(PlayerSpriteBundle {
+        position: get_position_from_percentage(spec.player.position.0, spec.player.position.1),
+        radius: Radius { value: spec.player.radius * *UNIT_RADIUS },
+        velocity: Velocity {
+            x: UomVelocity::new::<kilometer_per_second>(spec.player.velocity.0),
+            y: UomVelocity::new::<kilometer_per_second>(spec.player.velocity.1),
+        },
+        sprite: SpriteBundle {
+            texture: asset_server.load(ship_skin.sprite.as_str()),
+            ..Default::default()
+        },
+        ..Default::default()
+    });
+
+    // Spawn objects.
+
+    for body in spec.bodies.iter() {
+        let mut body_entity = commands.spawn(StaticPlanetBundle {
+            collision_response: body.collision,
+            position: get_position_from_percentage(body.position.0, body.position.1),
+            radius: Radius { value: body.radius * *UNIT_RADIUS },
+            mass: gameplay_scale.mass(body.mass * *MASS_OF_SUN),
+            sprite: SpriteBundle {
+                texture: asset_server.load(body.sprite.as_str()),
+                ..Default::default()
+            },
+            ..Default::default()
+        });
+
+        if let Some(visual_radius) = body.visual_radius {
+            body_entity.insert(VisualRadius { value: visual_radius * *UNIT_RADIUS });
+        }
+    }
+
+    // Spawn black holes.
+
+    for black_hole in spec.black_holes.iter() {
+        commands.spawn(BlackHoleBundle {
+            position: get_position_from_percentage(black_hole.position.0, black_hole.position.1),
+            radius: Radius { value: black_hole.radius * *UNIT_RADIUS },
+            mass: gameplay_scale.mass(black_hole.mass * *MASS_OF_SUN),
+            sprite: SpriteBundle {
+                sprite: Sprite {
+                    color: Color::BLACK,
+                    ..Default::default()
+                },
+                texture: asset_server.load(black_hole.sprite.as_str()),
+                ..Default::default()
+            },
+            ..Default::default()
+        });
+    }
+
+    // Spawn waypoints.
+
+    for (order, waypoint) in spec.waypoints.iter().enumerate() {
+        let mut waypoint_entity = commands.spawn(DestinationBundle {
+            destination: Destination::Waypoint { order: order as u32 },
+            position: get_position_from_percentage(waypoint.position.0, waypoint.position.1),
+            radius: Radius { value: waypoint.radius * *UNIT_RADIUS },
+            mass: gameplay_scale.mass(waypoint.mass * *MASS_OF_SUN),
+            sprite: SpriteBundle {
+                texture: asset_server.load(waypoint.sprite.as_str()),
+                ..Default::default()
+            },
+            ..Default::default()
+        });
+
+        if let Some(capture_radius) = waypoint.capture_radius {
+            waypoint_entity.insert(CaptureRadius { value: capture_radius * *UNIT_RADIUS });
+        }
+    }
+
+    // Spawn destination.
+
+    let destination = &spec.destination;
+
+    let mut destination_entity = commands.spawn(DestinationBundle {
+        position: get_position_from_percentage(destination.position.0, destination.position.1),
+        radius: Radius { value: destination.radius * *UNIT_RADIUS },
+        mass: gameplay_scale.mass(destination.mass * *MASS_OF_SUN),
+        sprite: SpriteBundle {
+            texture: asset_server.load(destination.sprite.as_str()),
+            ..Default::default()
+        },
+        ..Default::default()
+    });
+
+    if let Some(capture_radius) = destination.capture_radius {
+        destination_entity.insert(CaptureRadius { value: capture_radius * *UNIT_RADIUS });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        game::{black_hole::BlackHole, object::Planet, observer::Observer, player::shared::Player, shared::types::Position},
+        sim::headless_app,
+    };
+
+    fn body(position: (f64, f64)) -> BodySpec {
+        BodySpec {
+            position,
+            radius: 1.0,
+            mass: 0.5,
+            sprite: "sprites/planets/planet04.png".to_string(),
+            collision: CollisionResponse::default(),
+            visual_radius: None,
+            capture_radius: None,
+        }
+    }
+
+    /// A spec touching every field, with two bodies, a black hole, and two waypoints.
+    fn spec() -> LevelSpec {
+        LevelSpec {
+            player: PlayerSpec {
+                position: (0.1, 0.1),
+                velocity: (10.0, -5.0),
+                radius: 0.25,
+            },
+            bodies: vec![
+                BodySpec {
+                    collision: CollisionResponse::Bounce { restitution: 0.5 },
+                    visual_radius: Some(2.0),
+                    ..body((0.3, 0.5))
+                },
+                body((0.6, 0.2)),
+            ],
+            black_holes: vec![body((0.5, 0.8))],
+            waypoints: vec![
+                BodySpec {
+                    capture_radius: Some(1.5),
+                    ..body((0.4, 0.4))
+                },
+                body((0.7, 0.7)),
+            ],
+            destination: body((0.9, 0.9)),
+            time_compression: Some(0.5),
+            mass_factor: Some(1e6),
+            max_launch_velocity_fraction: Some(0.5),
+            power_curve: PowerCurve::Exponential { k: 2.0 },
+            launch_window: Some(LaunchWindow { open_at: 1.0, close_at: 3.0 }),
+            observer_time_limit: Some(100.0),
+            boundary_mode: BoundaryMode::Wrap,
+        }
+    }
+
+    fn count<F: bevy::ecs::query::ReadOnlyWorldQuery>(app: &mut App) -> usize {
+        app.world.query_filtered::<(), F>().iter(&app.world).count()
+    }
+
+    #[test]
+    fn level_spec_round_trips_through_ron() {
+        let spec = spec();
+        let contents = ron::ser::to_string_pretty(&spec, ron::ser::PrettyConfig::default()).unwrap();
+
+        assert_eq!(ron::from_str::<LevelSpec>(&contents).unwrap(), spec);
+    }
+
+    #[test]
+    fn spawn_level_from_spec_spawns_every_entity() {
+        let spec: LevelSpec = ron::from_str(&ron::ser::to_string(&spec()).unwrap()).unwrap();
+
+        let mut app = headless_app();
+        app.add_systems(Startup, move |commands: Commands, asset_server: Res<AssetServer>| {
+            spawn_level_from_spec(commands, asset_server, &ShipSkin::default(), &GameplayScale::default(), &spec);
+        });
+        app.update();
+
+        assert_eq!(count::<(With<Player>, With<Position>)>(&mut app), 1);
+        assert_eq!(count::<(With<Player>, Without<Position>)>(&mut app), 1);
+        assert_eq!(count::<With<Observer>>(&mut app), 1);
+        assert_eq!(count::<With<Planet>>(&mut app), 2);
+        assert_eq!(count::<With<BlackHole>>(&mut app), 1);
+        assert_eq!(count::<With<Destination>>(&mut app), 3);
+        assert_eq!(*app.world.resource::<TimeCompression>(), TimeCompression(0.5));
+        assert_eq!(app.world.resource::<WorldBounds>().mode, BoundaryMode::Wrap);
+    }
+
+    #[test]
+    fn example_level_parses() {
+        let spec: LevelSpec = ron::from_str(include_str!("../../../assets/levels/example.ron")).unwrap();
+
+        assert_eq!(spec.bodies.len(), 2);
+        assert_eq!(spec.boundary_mode, BoundaryMode::FailOnExit);
+    }
+}
@@ -0,0 +1,128 @@
+use bevy::prelude::*;
+
+use crate::shared::{SCREEN_HEIGHT_PX, SCREEN_WIDTH_PX};
+
+use super::{
+    destination::Destination,
+    player::shared::Player,
+    shared::{
+        helpers::get_translation_from_position,
+        types::{GameItem, GravitySource, Position},
+    },
+};
+
+const MINIMAP_WIDTH_PX: f32 = 192.0;
+const MINIMAP_HEIGHT_PX: f32 = 108.0;
+const MINIMAP_MARGIN_PX: f32 = 10.0;
+const MINIMAP_SCREENS_ACROSS: f32 = 3.0;
+
+// Components / bundles.
+
+#[derive(Component, Default)]
+pub struct Minimap;
+
+#[derive(Bundle, Default)]
+pub struct MinimapBundle {
+    pub item: GameItem,
+    pub minimap: Minimap,
+}
+
+// Startup systems.
+
+pub fn spawn_minimap(commands: &mut Commands) {
+    commands.spawn(MinimapBundle::default());
+}
+
+// Systems.
+
+pub fn minimap_update(
+    mut gizmos: Gizmos,
+    minimap_query: Query<(), With<Minimap>>,
+    camera_query: Query<(&Transform, &OrthographicProjection), With<Camera>>,
+    masses: Query<&Position, (With<GravitySource>, Without<Destination>)>,
+    destination_query: Query<&Position, With<Destination>>,
+    player_query: Query<&Position, With<Player>>,
+) {
+    if minimap_query.is_empty() {
+        return;
+    }
+
+    let Ok((camera_transform, projection)) = camera_query.get_single() else {
+        return;
+    };
+
+    // Anchor the minimap to the bottom-right corner of the view, keeping its on-screen size when zoomed.
+
+    let zoom = projection.scale;
+    let size = Vec2::new(MINIMAP_WIDTH_PX, MINIMAP_HEIGHT_PX) * zoom;
+    let margin = MINIMAP_MARGIN_PX * zoom;
+    let view_corner = camera_transform.translation.truncate() + Vec2::new(SCREEN_WIDTH_PX as f32, -SCREEN_HEIGHT_PX as f32) * zoom / 2.0;
+    let center = view_corner + Vec2::new(-margin - size.x / 2.0, margin + size.y / 2.0);
+
+    gizmos.rect_2d(center, 0.0, size, Color::rgba(1.0, 1.0, 1.0, 0.5));
+
+    // The minimap shows several screens' worth of space, centered on the level.
+
+    let screen_center = Vec2::new(SCREEN_WIDTH_PX as f32, SCREEN_HEIGHT_PX as f32) / 2.0;
+    let scale = size.x / (MINIMAP_SCREENS_ACROSS * SCREEN_WIDTH_PX as f32);
+
+    let to_minimap = |position: &Position| {
+        let offset = (get_translation_from_position(position).truncate() - screen_center) * scale;
+
+        center + offset.clamp(-size / 2.0, size / 2.0)
+    };
+
+    for position in masses.iter() {
+        gizmos.circle_2d(to_minimap(position), 2.0 * zoom, Color::ORANGE);
+    }
+
+    for position in destination_query.iter() {
+        gizmos.circle_2d(to_minimap(position), 3.0 * zoom, Color::GREEN);
+    }
+
+    for position in player_query.iter() {
+        gizmos.circle_2d(to_minimap(position), 2.0 * zoom, Color::WHITE);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        game::{
+            levels::{despawn_level, spawn_level, CurrentLevel},
+            player::player_sprite::ShipSkin,
+            shared::types::GameplayScale,
+        },
+        shared::state::AppState,
+    };
+
+    #[test]
+    fn minimap_spawns_on_game_entry_and_despawns_on_exit() {
+        let mut app = App::new();
+
+        app.add_plugins((MinimalPlugins, AssetPlugin::default()))
+            .add_state::<AppState>()
+            .init_resource::<ShipSkin>()
+            .init_resource::<GameplayScale>()
+            .init_resource::<CurrentLevel>()
+            .add_systems(OnEnter(AppState::InGame), spawn_level)
+            .add_systems(OnExit(AppState::InGame), despawn_level);
+
+        let minimap_count = |app: &mut App| app.world.query_filtered::<(), With<Minimap>>().iter(&app.world).count();
+
+        app.update();
+
+        assert_eq!(minimap_count(&mut app), 0);
+
+        app.world.resource_mut::<NextState<AppState>>().set(AppState::InGame);
+        app.update();
+
+        assert_eq!(minimap_count(&mut app), 1);
+
+        app.world.resource_mut::<NextState<AppState>>().set(AppState::Menu);
+        app.update();
+
+        assert_eq!(minimap_count(&mut app), 0);
+    }
+}
@@ -1,61 +1,308 @@
+pub mod assist;
+pub mod audio;
+pub mod black_hole;
+pub mod camera;
+pub mod debug;
 pub mod destination;
+pub mod failure;
+pub mod field;
+pub mod ghost;
+pub mod gravity_wave;
+pub mod heatmap;
+pub mod hint;
+pub mod insertion;
+pub mod launch_window;
 pub mod levels;
+pub mod minimap;
 pub mod object;
 pub mod observer;
+pub mod orbit_trace;
+pub mod outcome;
+pub mod particles;
+pub mod pause_menu;
 pub mod player;
+pub mod potential;
+pub mod preview;
+pub mod save;
+pub mod score;
 pub mod shared;
+pub mod starfield;
+pub mod warning;
 
-use bevy::prelude::*;
+use bevy::{ecs::schedule::SystemConfigs, prelude::*};
 
 use crate::shared::state::{AppState, GameState};
 
 use self::{
-    levels::{despawn_level, spawn_level},
-    observer::{observer_clock_text_update, observer_clock_update},
+    assist::{assist_toast_update, gravity_assist_check, gravity_assist_reset, AssistCounter, GravityAssistTracker},
+    audio::{audio_collision, audio_launch, audio_mute_check, audio_success, AudioConfig},
+    black_hole::accretion_ring_update,
+    camera::{camera_follow_toggle_check, camera_follow_update, camera_reset, CameraConfig},
+    debug::{substep_debug_toggle_check, substep_debug_update},
+    destination::{waypoint_progress_reset, WaypointProgress},
+    failure::{failure_text_dismiss, run_failed_update},
+    field::{field_arrows_toggle_check, field_arrows_update},
+    ghost::{ghost_finish, ghost_start, ghost_update, run_recording_update, BestRuns, RunRecording},
+    gravity_wave::{gravity_wave_emit_update, gravity_wave_update, GravityWaveConfig},
+    heatmap::{time_dilation_heatmap_toggle_check, time_dilation_heatmap_update},
+    hint::{hud_legend_toggle_check, launch_hint_dismiss, launch_hint_update, HintsShown},
+    insertion::{
+        circular_orbit_nudge_check, orbit_insertion_text_dismiss, orbit_insertion_toggle_check, orbit_insertion_update,
+        OrbitInsertionAssist,
+    },
+    launch_window::{aim_timer_update, launch_window_text_update, AimTimer},
+    levels::{despawn_level, level_cycle_check, level_fade_update, next_level_check, restart_level_check, spawn_level, DevMode},
+    minimap::minimap_update,
+    observer::{
+        clock_display_mode_check, clocks_reset, observer_clock_text_update, observer_clock_update, observer_eta_text_update,
+        observer_time_limit_check, unit_system_check,
+    },
+    orbit_trace::{orbit_trace_toggle_check, orbit_trace_update},
+    outcome::{launch_outcome_text_clear, launch_outcome_text_update, run_time_text_spawn},
+    particles::{particle_burst_collision, particle_burst_success, particle_update},
+    pause_menu::{pause_menu_interaction, pause_menu_toggle_check, PauseMenu},
     player::{
-        player_clock::{player_clock_text_update, player_clock_update},
-        player_sprite::player_launch,
+        player_clock::{
+            gamma_breakdown_text_update, gamma_breakdown_toggle_check, grav_frame_check, player_clock_text_update, player_clock_update,
+            player_energy_text_update, player_orbit_text_update, player_velocity_text_update,
+        },
+        player_sprite::{
+            launch_aim_update, player_doppler_tint_update, player_keyboard_launch, player_launch, player_launch_preview,
+            player_length_contraction_update, player_scripted_launch, player_thrust_update, KeyboardAim, LaunchAim, LaunchConfig, ShipSkin,
+        },
     },
-    shared::systems::{
-        exit_level_check, planet_scale_update, position_update, rocket_rotation_update, rocket_scale_update, translation_update,
-        velocity_update, collision_check,
+    potential::{potential_cross_section_toggle_check, potential_cross_section_update},
+    preview::{trajectory_preview_update, TrajectoryPreview},
+    save::{load_session_check, save_session_check},
+    score::{level_timer_finish, level_timer_reset, level_timer_update, BestTimes, LevelTimer},
+    shared::{
+        constants::PHYSICS_TIMESTEP_SECONDS,
+        systems::{
+            boundary_check, collision_check, handle_collision_events, integrate_motion, launch_origin_record, pause_toggle_check,
+            planet_scale_update, rocket_rotation_update, rocket_scale_update, translation_update,
+        },
+        types::{
+            ClockDisplayMode, CollisionEvent, GameplayScale, GravFrame, HudConfig, KeyBindings, LaunchOrigin, PhysicsConfig, RunFailed,
+            SimRng, SimSeed, TimeCompression, UnitSystem, VisualsConfig, WorldBounds,
+        },
     },
+    starfield::{aberration_update, despawn_starfield, spawn_starfield, StarfieldConfig},
+    warning::{collision_warning_update, planet_danger_glow_update},
 };
 
+/// The fixed physics step: motion, bounds, collisions, and clocks. `sim::run_headless` runs the same systems, so a
+/// headless run steps exactly like the game.
+pub fn physics_systems() -> SystemConfigs {
+    (
+        integrate_motion,
+        boundary_check.after(integrate_motion),
+        collision_check.after(boundary_check),
+        handle_collision_events.after(collision_check),
+        observer_clock_update,
+        observer_time_limit_check.after(observer_clock_update),
+        player_clock_update.after(integrate_motion),
+    )
+        .into_configs()
+}
+
 pub struct GamePlugin;
 
 impl Plugin for GamePlugin {
     fn build(&self, app: &mut App) {
         app.add_state::<GameState>()
+            .insert_resource(FixedTime::new_from_secs(PHYSICS_TIMESTEP_SECONDS))
+            .init_resource::<LevelTimer>()
+            .init_resource::<BestTimes>()
+            .init_resource::<WorldBounds>()
+            .init_resource::<LaunchOrigin>()
+            .init_resource::<AudioConfig>()
+            .init_resource::<ClockDisplayMode>()
+            .init_resource::<VisualsConfig>()
+            .init_resource::<KeyboardAim>()
+            .init_resource::<LaunchAim>()
+            .init_resource::<KeyBindings>()
+            .init_resource::<ShipSkin>()
+            .init_resource::<LaunchConfig>()
+            .init_resource::<RunRecording>()
+            .init_resource::<BestRuns>()
+            .init_resource::<WaypointProgress>()
+            .init_resource::<CameraConfig>()
+            .init_resource::<TimeCompression>()
+            .init_resource::<PhysicsConfig>()
+            .init_resource::<GameplayScale>()
+            .init_resource::<AssistCounter>()
+            .init_resource::<GravityAssistTracker>()
+            .init_resource::<HintsShown>()
+            .init_resource::<GravFrame>()
+            .init_resource::<AimTimer>()
+            .init_resource::<StarfieldConfig>()
+            .init_resource::<SimSeed>()
+            .init_resource::<SimRng>()
+            .init_resource::<UnitSystem>()
+            .init_resource::<DevMode>()
+            .init_resource::<GravityWaveConfig>()
+            .init_resource::<OrbitInsertionAssist>()
+            .init_resource::<HudConfig>()
+            .init_resource::<TrajectoryPreview>()
+            .add_event::<CollisionEvent>()
+            .add_event::<RunFailed>()
             // Spawn things on enter.
-            .add_systems(OnEnter(AppState::InGame), spawn_level)
+            .add_systems(OnEnter(AppState::InGame), (spawn_level, spawn_starfield))
             // Destroy things on exit.
-            .add_systems(OnExit(AppState::InGame), despawn_level)
+            .add_systems(OnExit(AppState::InGame), (despawn_level, despawn_starfield, camera_reset))
             // Run the scale updates always.
             .add_systems(
                 Update,
-                (planet_scale_update, rocket_scale_update, exit_level_check).run_if(in_state(AppState::InGame)),
+                (
+                    planet_scale_update,
+                    rocket_scale_update,
+                    player_length_contraction_update.after(rocket_scale_update),
+                    pause_menu_toggle_check,
+                    pause_menu_interaction,
+                    restart_level_check.run_if(in_state(GameState::Paused).or_else(in_state(GameState::Running))),
+                    pause_toggle_check.run_if(not(any_with_component::<PauseMenu>())),
+                    audio_mute_check,
+                    audio_collision,
+                    particle_burst_collision,
+                    particle_update,
+                    clock_display_mode_check,
+                    grav_frame_check,
+                    unit_system_check,
+                    save_session_check,
+                    load_session_check,
+                    assist_toast_update,
+                    launch_window_text_update,
+                )
+                    .run_if(in_state(AppState::InGame)),
+            )
+            // Run the camera and overlays always.
+            .add_systems(
+                Update,
+                (
+                    camera_follow_toggle_check,
+                    camera_follow_update.after(camera_follow_toggle_check),
+                    aberration_update.after(camera_follow_update),
+                    minimap_update.after(camera_follow_update),
+                    potential_cross_section_toggle_check,
+                    potential_cross_section_update
+                        .after(potential_cross_section_toggle_check)
+                        .after(camera_follow_update),
+                    time_dilation_heatmap_toggle_check,
+                    time_dilation_heatmap_update.after(time_dilation_heatmap_toggle_check),
+                    accretion_ring_update,
+                    orbit_trace_toggle_check,
+                    orbit_trace_update.after(orbit_trace_toggle_check),
+                    field_arrows_toggle_check,
+                    field_arrows_update.after(field_arrows_toggle_check),
+                    gravity_wave_emit_update,
+                    gravity_wave_update.after(gravity_wave_emit_update),
+                    trajectory_preview_update.after(launch_aim_update),
+                )
+                    .run_if(in_state(AppState::InGame)),
+            )
+            // React to launches and finishes.
+            .add_systems(
+                OnTransition {
+                    from: GameState::Paused,
+                    to: GameState::Running,
+                },
+                (
+                    level_timer_reset,
+                    audio_launch,
+                    ghost_start,
+                    waypoint_progress_reset,
+                    launch_hint_dismiss,
+                    gravity_assist_reset,
+                    orbit_insertion_text_dismiss,
+                    launch_outcome_text_clear,
+                    clocks_reset,
+                    failure_text_dismiss,
+                    launch_origin_record,
+                ),
+            )
+            .add_systems(
+                OnEnter(GameState::Finished),
+                (
+                    level_timer_finish,
+                    run_time_text_spawn.after(level_timer_finish),
+                    ghost_finish.after(level_timer_finish),
+                    audio_success,
+                    particle_burst_success,
+                ),
+            )
+            // Run the shortcuts and their effects always.
+            .add_systems(
+                Update,
+                (
+                    level_cycle_check,
+                    level_fade_update,
+                    run_failed_update,
+                    hud_legend_toggle_check,
+                    gamma_breakdown_toggle_check,
+                    gamma_breakdown_text_update.after(gamma_breakdown_toggle_check),
+                    substep_debug_toggle_check,
+                    substep_debug_update.after(substep_debug_toggle_check),
+                )
+                    .run_if(in_state(AppState::InGame)),
+            )
+            // Allow moving on once finished.
+            .add_systems(
+                Update,
+                next_level_check
+                    .run_if(in_state(AppState::InGame))
+                    .run_if(in_state(GameState::Finished)),
             )
             // Allow launching if paused.
             .add_systems(
                 Update,
-                (player_launch, translation_update)
+                (
+                    player_launch,
+                    player_keyboard_launch,
+                    launch_aim_update.after(player_keyboard_launch),
+                    orbit_insertion_toggle_check,
+                    orbit_insertion_update
+                        .after(orbit_insertion_toggle_check)
+                        .before(player_keyboard_launch),
+                    player_launch_preview,
+                    launch_outcome_text_update.after(trajectory_preview_update),
+                    player_scripted_launch,
+                    launch_hint_update,
+                    translation_update,
+                    aim_timer_update,
+                )
                     .run_if(in_state(AppState::InGame))
                     .run_if(in_state(GameState::Paused)),
             )
+            // Run the physics on a fixed timestep if running.
+            .add_systems(
+                FixedUpdate,
+                (
+                    physics_systems(),
+                    player_thrust_update.before(integrate_motion),
+                    run_recording_update.after(integrate_motion),
+                    gravity_assist_check.after(integrate_motion),
+                    ghost_update,
+                )
+                    .run_if(in_state(AppState::InGame))
+                    .run_if(in_state(GameState::Running)),
+            )
             // Run the rest of the updates if running.
             .add_systems(
                 Update,
                 (
                     rocket_rotation_update,
-                    velocity_update,
-                    position_update.after(velocity_update),
-                    translation_update.after(position_update),
-                    collision_check,
-                    observer_clock_update,
-                    observer_clock_text_update.after(observer_clock_update),
-                    player_clock_update,
-                    player_clock_text_update.after(player_clock_update),
+                    player_doppler_tint_update,
+                    translation_update,
+                    observer_clock_text_update,
+                    observer_eta_text_update.after(trajectory_preview_update),
+                    collision_warning_update.after(trajectory_preview_update),
+                    planet_danger_glow_update,
+                    circular_orbit_nudge_check,
+                    player_clock_text_update,
+                    player_orbit_text_update,
+                    player_energy_text_update,
+                    player_velocity_text_update,
+                    level_timer_update,
                 )
                     .run_if(in_state(AppState::InGame))
                     .run_if(in_state(GameState::Running)),
@@ -1,4 +1,4 @@
-use super::shared::types::{GameItem, Mass, PlanetSprite, Position, Radius, Velocity};
+use super::shared::types::{CollisionResponse, GameItem, GravityAffected, GravitySource, Mass, PlanetSprite, Position, Radius, Velocity};
 use bevy::prelude::*;
 
 #[derive(Component, Default)]
@@ -8,6 +8,8 @@ pub struct Planet;
 pub struct StaticPlanetBundle {
     pub item: GameItem,
     pub planet: Planet,
+    pub collision_response: CollisionResponse,
+    pub gravity_source: GravitySource,
     pub position: Position,
     pub mass: Mass,
     pub radius: Radius,
@@ -19,6 +21,9 @@ pub struct StaticPlanetBundle {
 pub struct DynamicPlanetBundle {
     pub item: GameItem,
     pub planet: Planet,
+    pub collision_response: CollisionResponse,
+    pub gravity_source: GravitySource,
+    pub gravity_affected: GravityAffected,
     pub position: Position,
     pub mass: Mass,
     pub radius: Radius,
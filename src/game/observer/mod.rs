@@ -1,13 +1,22 @@
 use bevy::prelude::*;
+use uom::si::{f64::Time as UomTime, time::second};
 
-use super::shared::{
-    constants::DAYS_PER_SECOND_UOM,
-    types::{Clock, GameItem},
+use super::{
+    player::shared::Player,
+    preview::TrajectoryPreview,
+    shared::{
+        helpers::{format_time, format_time_difference},
+        types::{Action, Clock, ClockDisplayMode, GameItem, KeyBindings, RunFailed, TimeCompression, UnitSystem},
+    },
 };
 
 #[derive(Component, Default)]
 pub struct Observer;
 
+/// The observer time by which the player must arrive, or fail.
+#[derive(Resource, Clone, Copy, Debug)]
+pub struct ObserverTimeLimit(pub UomTime);
+
 #[derive(Bundle, Default)]
 pub struct ObserverClockBundle {
     pub item: GameItem,
@@ -17,14 +26,16 @@ pub struct ObserverClockBundle {
 }
 
 pub fn spawn_observer_clock(commands: &mut Commands, asset_server: &Res<AssetServer>) {
-    let clock_text = TextBundle::from_section(
-        "t_o = 00.00",
-        TextStyle {
-            font_size: 40.0,
-            font: asset_server.load("fonts/HackNerdFontMono-Regular.ttf"),
-            ..Default::default()
-        },
-    )
+    let style = TextStyle {
+        font_size: 40.0,
+        font: asset_server.load("fonts/HackNerdFontMono-Regular.ttf"),
+        ..Default::default()
+    };
+
+    let clock_text = TextBundle::from_sections([
+        TextSection::new("t_o = 00.00", style.clone()),
+        TextSection::new("\nETA = --", style),
+    ])
     .with_style(Style {
         position_type: PositionType::Absolute,
         top: Val::Px(10.0),
@@ -37,18 +48,139 @@ pub fn spawn_observer_clock(commands: &mut Commands, asset_server: &Res<AssetSer
 
 // Clock systems.
 
-pub fn observer_clock_update(mut query: Query<&mut Clock, With<Observer>>, time: Res<Time>) {
-    let time_elapsed = *DAYS_PER_SECOND_UOM * time.delta_seconds() as f64;
+pub fn observer_clock_update(
+    mut query: Query<&mut Clock, With<Observer>>,
+    fixed_time: Res<FixedTime>,
+    time_compression: Res<TimeCompression>,
+) {
+    let time_elapsed = time_compression.time_elapsed(fixed_time.period.as_secs_f64());
 
     let mut clock = query.single_mut();
 
     clock.value += time_elapsed;
 }
 
-pub fn observer_clock_text_update(mut query: Query<(&mut Text, &Clock), With<Observer>>) {
+pub fn observer_clock_text_update(
+    mut query: Query<(&mut Text, &Clock), With<Observer>>,
+    player_clock_query: Query<&Clock, (With<Player>, Without<Observer>)>,
+    clock_display_mode: Res<ClockDisplayMode>,
+    unit_system: Res<UnitSystem>,
+    observer_time_limit: Option<Res<ObserverTimeLimit>>,
+) {
     let (mut text, clock) = query.single_mut();
 
-    let days = clock.value.value / 24.0 / 3600.0;
+    let readout = match *clock_display_mode {
+        ClockDisplayMode::Both => format!("t_o = {}", format_time(clock.value, *unit_system)),
+        ClockDisplayMode::Difference => format_time_difference(clock.value, player_clock_query.single().value, *unit_system),
+    };
+
+    text.sections[0].value = match observer_time_limit {
+        Some(observer_time_limit) => {
+            let remaining = (observer_time_limit.0 - clock.value).max(UomTime::new::<second>(0.0));
+
+            format!("{} left = {}", readout, format_time(remaining, *unit_system))
+        }
+        None => readout,
+    };
+}
+
+pub fn observer_time_limit_check(
+    query: Query<&Clock, With<Observer>>,
+    observer_time_limit: Option<Res<ObserverTimeLimit>>,
+    mut run_failed_events: EventWriter<RunFailed>,
+) {
+    let Some(observer_time_limit) = observer_time_limit else {
+        return;
+    };
+
+    let Ok(clock) = query.get_single() else {
+        return;
+    };
+
+    if clock.value > observer_time_limit.0 {
+        run_failed_events.send(RunFailed { reason: "out of time" });
+    }
+}
+
+/// Restarts both clocks on every launch, so a retry gets the whole time limit again.
+#[allow(clippy::type_complexity)]
+pub fn clocks_reset(mut query: Query<&mut Clock, Or<(With<Observer>, With<Player>)>>) {
+    for mut clock in query.iter_mut() {
+        clock.value = UomTime::default();
+    }
+}
+
+pub fn observer_eta_text_update(
+    mut query: Query<&mut Text, With<Observer>>,
+    trajectory_preview: Res<TrajectoryPreview>,
+    time_compression: Res<TimeCompression>,
+    unit_system: Res<UnitSystem>,
+) {
+    let Ok(mut text) = query.get_single_mut() else {
+        return;
+    };
+
+    text.sections[1].value = match trajectory_preview.arrival(&time_compression) {
+        Some(time) => format!("\nETA = {}", format_time(time, *unit_system)),
+        None => "\nETA = --".to_string(),
+    };
+}
+
+pub fn clock_display_mode_check(
+    keyboard_input: Res<Input<KeyCode>>,
+    key_bindings: Res<KeyBindings>,
+    mut clock_display_mode: ResMut<ClockDisplayMode>,
+) {
+    if !key_bindings.just_pressed(&keyboard_input, Action::ToggleClockDisplay) {
+        return;
+    }
+
+    *clock_display_mode = match *clock_display_mode {
+        ClockDisplayMode::Both => ClockDisplayMode::Difference,
+        ClockDisplayMode::Difference => ClockDisplayMode::Both,
+    };
+}
+
+pub fn unit_system_check(keyboard_input: Res<Input<KeyCode>>, key_bindings: Res<KeyBindings>, mut unit_system: ResMut<UnitSystem>) {
+    if !key_bindings.just_pressed(&keyboard_input, Action::ToggleUnits) {
+        return;
+    }
+
+    *unit_system = match *unit_system {
+        UnitSystem::SI => UnitSystem::Natural,
+        UnitSystem::Natural => UnitSystem::SI,
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use uom::si::time::day;
+
+    fn observer_days_after(time_compression: f64, frames: usize) -> f64 {
+        let mut world = World::new();
+
+        world.insert_resource(FixedTime::new_from_secs(0.5));
+        world.insert_resource(TimeCompression(time_compression));
+
+        let observer = world.spawn((Observer, Clock::default())).id();
+
+        let mut schedule = Schedule::default();
+        schedule.add_systems(observer_clock_update);
+
+        for _ in 0..frames {
+            schedule.run(&mut world);
+        }
+
+        world.get::<Clock>(observer).unwrap().value.get::<day>()
+    }
+
+    #[test]
+    fn observer_clock_scales_linearly_with_time_compression() {
+        let base = observer_days_after(0.5, 10);
 
-    text.sections[0].value = format!("t_o = {:2.2}", days);
+        assert!((base - 2.5).abs() < 1e-9);
+        assert!((observer_days_after(1.0, 10) - 2.0 * base).abs() < 1e-9);
+        assert!((observer_days_after(2.0, 10) - 4.0 * base).abs() < 1e-9);
+    }
 }
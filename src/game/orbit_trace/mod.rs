@@ -0,0 +1,69 @@
+use bevy::prelude::*;
+
+use super::{
+    player::shared::Player,
+    shared::{
+        constants::{ORBIT_TRACE_STEPS, TRAJECTORY_PREVIEW_STEP_SECONDS},
+        helpers::{get_translation_from_position, trace_orbit},
+        types::{
+            Action, GravitySource, InfluenceRadius, KeyBindings, Mass, PhysicsConfig, Position, TimeCompression, Velocity, VisualsConfig,
+        },
+    },
+};
+
+// Systems.
+
+pub fn orbit_trace_toggle_check(
+    keyboard_input: Res<Input<KeyCode>>,
+    key_bindings: Res<KeyBindings>,
+    mut visuals_config: ResMut<VisualsConfig>,
+) {
+    if key_bindings.just_pressed(&keyboard_input, Action::ToggleOrbitTrace) {
+        visuals_config.orbit_trace = !visuals_config.orbit_trace;
+    }
+}
+
+pub fn orbit_trace_update(
+    mut gizmos: Gizmos,
+    visuals_config: Res<VisualsConfig>,
+    time_compression: Res<TimeCompression>,
+    physics_config: Res<PhysicsConfig>,
+    player_query: Query<(Entity, &Position, &Velocity), With<Player>>,
+    masses: Query<(Entity, &Position, &Mass, Option<&InfluenceRadius>), With<GravitySource>>,
+) {
+    if !visuals_config.orbit_trace {
+        return;
+    }
+
+    let Ok((player_entity, player_position, player_velocity)) = player_query.get_single() else {
+        return;
+    };
+
+    let masses: Vec<_> = masses
+        .iter()
+        .map(|(entity, position, mass, influence_radius)| (entity, *position, *mass, influence_radius.copied()))
+        .collect();
+    let time_step = time_compression.time_elapsed(TRAJECTORY_PREVIEW_STEP_SECONDS);
+
+    let trace = trace_orbit(
+        player_entity,
+        *player_position,
+        *player_velocity,
+        &masses,
+        time_step,
+        physics_config.softening,
+        ORBIT_TRACE_STEPS,
+    );
+
+    // Fade the path out with distance along it.
+
+    let segments = trace.len().saturating_sub(1).max(1) as f32;
+
+    for (index, segment) in trace.windows(2).enumerate() {
+        let start = get_translation_from_position(&segment[0]).truncate();
+        let end = get_translation_from_position(&segment[1]).truncate();
+        let alpha = 0.8 * (1.0 - index as f32 / segments);
+
+        gizmos.line_2d(start, end, Color::rgba(0.4, 0.8, 1.0, alpha));
+    }
+}
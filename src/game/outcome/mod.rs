@@ -0,0 +1,94 @@
+use bevy::prelude::*;
+
+use super::{
+    levels::{CurrentLevel, LevelList},
+    preview::TrajectoryPreview,
+    score::LevelTimer,
+    shared::types::{GameItem, LaunchOutcome},
+};
+
+// Components.
+
+#[derive(Component, Default)]
+pub struct LaunchOutcomeText;
+
+#[derive(Component, Default)]
+pub struct RunTimeText;
+
+// Startup systems.
+
+pub fn spawn_launch_outcome_text(commands: &mut Commands, asset_server: &Res<AssetServer>) {
+    let text = TextBundle::from_section(
+        "",
+        TextStyle {
+            font_size: 30.0,
+            font: asset_server.load("fonts/HackNerdFontMono-Regular.ttf"),
+            ..Default::default()
+        },
+    )
+    .with_style(Style {
+        position_type: PositionType::Absolute,
+        bottom: Val::Px(45.0),
+        left: Val::Px(10.0),
+        ..Default::default()
+    });
+
+    commands.spawn((GameItem, LaunchOutcomeText, text));
+}
+
+// Systems.
+
+pub fn launch_outcome_text_update(mut query: Query<&mut Text, With<LaunchOutcomeText>>, trajectory_preview: Res<TrajectoryPreview>) {
+    let Ok(mut text) = query.get_single_mut() else {
+        return;
+    };
+
+    let Some(outcome) = trajectory_preview.outcome else {
+        return;
+    };
+
+    text.sections[0].value = format!("outcome = {}", outcome.label());
+    text.sections[0].style.color = match outcome {
+        LaunchOutcome::Hit => Color::GREEN,
+        LaunchOutcome::CrashInto(_) => Color::RED,
+        LaunchOutcome::Escaped | LaunchOutcome::Orbiting => Color::YELLOW,
+    };
+}
+
+pub fn launch_outcome_text_clear(mut query: Query<&mut Text, With<LaunchOutcomeText>>) {
+    for mut text in query.iter_mut() {
+        text.sections[0].value.clear();
+    }
+}
+
+pub fn run_time_text_spawn(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    level_timer: Res<LevelTimer>,
+    current_level: Res<CurrentLevel>,
+    level_list: Res<LevelList>,
+) {
+    let suffix = if level_timer.is_best { " (best!)" } else { "" };
+    let next = if current_level.next(&level_list).is_some() {
+        "next level"
+    } else {
+        "menu"
+    };
+
+    let run_time_text = TextBundle::from_section(
+        format!("t = {:.2}s{}\nN: {}", level_timer.seconds, suffix, next),
+        TextStyle {
+            font_size: 40.0,
+            font: asset_server.load("fonts/HackNerdFontMono-Regular.ttf"),
+            ..Default::default()
+        },
+    )
+    .with_style(Style {
+        position_type: PositionType::Absolute,
+        bottom: Val::Px(10.0),
+        left: Val::Px(10.0),
+        ..Default::default()
+    });
+
+    commands.spawn((GameItem, RunTimeText, run_time_text));
+}
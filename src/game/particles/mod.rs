@@ -0,0 +1,164 @@
+use bevy::prelude::*;
+use rand::Rng;
+
+use super::{
+    object::Planet,
+    player::shared::Player,
+    shared::types::{CollisionEvent, CollisionKind, CollisionResponse, GameItem, SimRng},
+};
+
+const PARTICLE_COUNT: usize = 24;
+const PARTICLE_LIFETIME_SECONDS: f32 = 1.0;
+const PARTICLE_SIZE_PX: f32 = 4.0;
+const PARTICLE_SPEED_PX: f32 = 120.0;
+const PARTICLE_Z: f32 = 1.0;
+const CRASH_COLOR: Color = Color::rgb(1.0, 0.4, 0.1);
+const SUCCESS_COLOR: Color = Color::rgb(0.3, 1.0, 0.4);
+
+// Components / bundles.
+
+#[derive(Component)]
+pub struct Particle {
+    pub lifetime: Timer,
+    /// Drift in pixels per second.
+    pub velocity: Vec2,
+}
+
+#[derive(Bundle)]
+pub struct ParticleBundle {
+    pub item: GameItem,
+    pub particle: Particle,
+    pub sprite: SpriteBundle,
+}
+
+// Helpers.
+
+fn spawn_particle_burst(commands: &mut Commands, rng: &mut SimRng, center: Vec2, color: Color) {
+    for _ in 0..PARTICLE_COUNT {
+        let direction = Vec2::from_angle(rng.0.gen_range(0.0..std::f32::consts::TAU));
+        let speed = PARTICLE_SPEED_PX * rng.0.gen_range(0.5..1.0);
+
+        commands.spawn(ParticleBundle {
+            item: GameItem,
+            particle: Particle {
+                lifetime: Timer::from_seconds(PARTICLE_LIFETIME_SECONDS, TimerMode::Once),
+                velocity: direction * speed,
+            },
+            sprite: SpriteBundle {
+                sprite: Sprite {
+                    color,
+                    custom_size: Some(Vec2::splat(PARTICLE_SIZE_PX)),
+                    ..Default::default()
+                },
+                transform: Transform::from_translation(center.extend(PARTICLE_Z)),
+                ..Default::default()
+            },
+        });
+    }
+}
+
+// Systems.
+
+pub fn particle_burst_collision(
+    mut commands: Commands,
+    mut rng: ResMut<SimRng>,
+    mut collision_events: EventReader<CollisionEvent>,
+    player_query: Query<&Transform, With<Player>>,
+    planet_query: Query<&CollisionResponse, With<Planet>>,
+) {
+    // Only failures burst, and only once per frame, however many fixed steps reported the crash.
+
+    let has_failed = collision_events.iter().any(|collision_event| match collision_event.kind {
+        CollisionKind::Planet => planet_query
+            .get(collision_event.other)
+            .is_ok_and(|collision_response| *collision_response == CollisionResponse::Fail),
+        CollisionKind::Horizon => true,
+        CollisionKind::Destination => false,
+    });
+
+    if !has_failed {
+        return;
+    }
+
+    let Ok(player_transform) = player_query.get_single() else {
+        return;
+    };
+
+    spawn_particle_burst(&mut commands, &mut rng, player_transform.translation.truncate(), CRASH_COLOR);
+}
+
+pub fn particle_burst_success(mut commands: Commands, mut rng: ResMut<SimRng>, player_query: Query<&Transform, With<Player>>) {
+    let Ok(player_transform) = player_query.get_single() else {
+        return;
+    };
+
+    spawn_particle_burst(&mut commands, &mut rng, player_transform.translation.truncate(), SUCCESS_COLOR);
+}
+
+pub fn particle_update(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut particle_query: Query<(Entity, &mut Particle, &mut Transform, &mut Sprite)>,
+) {
+    for (entity, mut particle, mut transform, mut sprite) in particle_query.iter_mut() {
+        particle.lifetime.tick(time.delta());
+
+        if particle.lifetime.finished() {
+            commands.entity(entity).despawn_recursive();
+            continue;
+        }
+
+        transform.translation += (particle.velocity * time.delta_seconds()).extend(0.0);
+        sprite.color.set_a(particle.lifetime.percent_left());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game::shared::types::SimSeed;
+    use bevy::utils::{Duration, Instant};
+
+    #[test]
+    fn crash_bursts_particles_that_fade_out() {
+        let mut world = World::new();
+        let start = Instant::now();
+
+        world.insert_resource(SimRng::new(SimSeed(1)));
+        world.insert_resource(Time::new(start));
+        world.init_resource::<Events<CollisionEvent>>();
+        world.resource_mut::<Time>().update_with_instant(start);
+
+        let player = world.spawn((Player, Transform::default())).id();
+        let planet = world.spawn((Planet, CollisionResponse::Fail)).id();
+
+        world.send_event(CollisionEvent {
+            player,
+            other: planet,
+            kind: CollisionKind::Planet,
+        });
+
+        let mut burst_schedule = Schedule::default();
+        burst_schedule.add_systems(particle_burst_collision);
+        burst_schedule.run(&mut world);
+
+        assert_eq!(world.query::<&Particle>().iter(&world).count(), PARTICLE_COUNT);
+
+        // Step through the lifetime a quarter at a time; the particles last until the very end of it.
+
+        let mut update_schedule = Schedule::default();
+        update_schedule.add_systems(particle_update);
+
+        let step = Duration::from_secs_f32(PARTICLE_LIFETIME_SECONDS / 4.0);
+
+        for quarter in 1..=4 {
+            let last_update = world.resource::<Time>().last_update().unwrap();
+            world.resource_mut::<Time>().update_with_instant(last_update + step);
+            update_schedule.run(&mut world);
+
+            let expected = if quarter < 4 { PARTICLE_COUNT } else { 0 };
+
+            assert_eq!(world.query::<&Particle>().iter(&world).count(), expected);
+        }
+    }
+}
@@ -0,0 +1,223 @@
+use bevy::prelude::*;
+
+use crate::shared::state::{AppState, GameState};
+
+use super::{
+    levels::{respawn_level, CurrentLevel},
+    player::player_sprite::ShipSkin,
+    shared::types::{Action, GameItem, GameplayScale, KeyBindings},
+};
+
+// Components.
+
+#[derive(Component)]
+pub struct PauseMenu {
+    /// The state to return to when the menu is closed.
+    pub prior: GameState,
+}
+
+#[derive(Component, Clone, Copy, PartialEq, Eq)]
+pub enum PauseMenuButton {
+    Resume,
+    Restart,
+    Quit,
+}
+
+impl PauseMenuButton {
+    pub fn label(&self) -> &'static str {
+        match self {
+            PauseMenuButton::Resume => "Resume",
+            PauseMenuButton::Restart => "Restart",
+            PauseMenuButton::Quit => "Quit to Menu",
+        }
+    }
+}
+
+// Helpers.
+
+fn spawn_pause_menu(commands: &mut Commands, asset_server: &AssetServer, prior: GameState) {
+    let text_style = TextStyle {
+        font_size: 40.0,
+        font: asset_server.load("fonts/HackNerdFontMono-Regular.ttf"),
+        ..Default::default()
+    };
+
+    let root = NodeBundle {
+        style: Style {
+            width: Val::Percent(100.0),
+            height: Val::Percent(100.0),
+            flex_direction: FlexDirection::Column,
+            align_items: AlignItems::Center,
+            justify_content: JustifyContent::Center,
+            row_gap: Val::Px(10.0),
+            ..Default::default()
+        },
+        background_color: Color::rgba(0.0, 0.0, 0.0, 0.6).into(),
+        z_index: ZIndex::Global(10),
+        ..Default::default()
+    };
+
+    commands.spawn((GameItem, PauseMenu { prior }, root)).with_children(|parent| {
+        parent.spawn(TextBundle::from_section("Paused", text_style.clone()));
+
+        for button in [PauseMenuButton::Resume, PauseMenuButton::Restart, PauseMenuButton::Quit] {
+            let button_bundle = ButtonBundle {
+                style: Style {
+                    padding: UiRect::all(Val::Px(10.0)),
+                    ..Default::default()
+                },
+                background_color: Color::rgb(0.15, 0.15, 0.15).into(),
+                ..Default::default()
+            };
+
+            parent.spawn((button, button_bundle)).with_children(|parent| {
+                parent.spawn(TextBundle::from_section(button.label(), text_style.clone()));
+            });
+        }
+    });
+}
+
+// Systems.
+
+pub fn pause_menu_toggle_check(
+    mut commands: Commands,
+    keyboard_input: Res<Input<KeyCode>>,
+    key_bindings: Res<KeyBindings>,
+    asset_server: Res<AssetServer>,
+    game_state: Res<State<GameState>>,
+    mut next_game_state: ResMut<NextState<GameState>>,
+    menu_query: Query<(Entity, &PauseMenu)>,
+) {
+    if !key_bindings.just_pressed(&keyboard_input, Action::PauseMenu) {
+        return;
+    }
+
+    if let Ok((menu_entity, menu)) = menu_query.get_single() {
+        commands.entity(menu_entity).despawn_recursive();
+        next_game_state.set(menu.prior.clone());
+    } else if *game_state.get() != GameState::Finished {
+        // Once finished, returning to `Finished` would rerun everything that reacts to entering it, so the menu stays
+        // closed; N already moves on from there.
+        spawn_pause_menu(&mut commands, &asset_server, game_state.get().clone());
+        next_game_state.set(GameState::SimPaused);
+    }
+}
+
+#[allow(clippy::too_many_arguments)]
+pub fn pause_menu_interaction(
+    mut commands: Commands,
+    mut mouse_input: ResMut<Input<MouseButton>>,
+    mut button_query: Query<(&Interaction, &PauseMenuButton, &mut BackgroundColor), Changed<Interaction>>,
+    menu_query: Query<(Entity, &PauseMenu)>,
+    game_item_query: Query<Entity, With<GameItem>>,
+    asset_server: Res<AssetServer>,
+    ship_skin: Res<ShipSkin>,
+    gameplay_scale: Res<GameplayScale>,
+    current_level: Res<CurrentLevel>,
+    mut next_game_state: ResMut<NextState<GameState>>,
+    mut next_app_state: ResMut<NextState<AppState>>,
+) {
+    let Ok((menu_entity, menu)) = menu_query.get_single() else {
+        return;
+    };
+
+    for (interaction, button, mut background_color) in button_query.iter_mut() {
+        match interaction {
+            Interaction::Pressed => {
+                // Swallow the click, so it doesn't also launch the player.
+
+                mouse_input.release_all();
+                mouse_input.reset_all();
+
+                match button {
+                    PauseMenuButton::Resume => {
+                        commands.entity(menu_entity).despawn_recursive();
+                        next_game_state.set(menu.prior.clone());
+                    }
+                    PauseMenuButton::Restart => {
+                        respawn_level(
+                            commands,
+                            asset_server,
+                            &ship_skin,
+                            &gameplay_scale,
+                            &game_item_query,
+                            &mut next_game_state,
+                            &current_level,
+                        );
+                    }
+                    PauseMenuButton::Quit => {
+                        next_app_state.set(AppState::Menu);
+                        next_game_state.set(GameState::Paused);
+                    }
+                }
+
+                return;
+            }
+            Interaction::Hovered => *background_color = Color::rgb(0.3, 0.3, 0.3).into(),
+            Interaction::None => *background_color = Color::rgb(0.15, 0.15, 0.15).into(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn pause_menu_app() -> App {
+        let mut app = App::new();
+
+        app.add_plugins((MinimalPlugins, AssetPlugin::default()))
+            .add_state::<AppState>()
+            .add_state::<GameState>()
+            .init_resource::<Input<KeyCode>>()
+            .init_resource::<Input<MouseButton>>()
+            .init_resource::<KeyBindings>()
+            .init_resource::<ShipSkin>()
+            .init_resource::<GameplayScale>()
+            .init_resource::<CurrentLevel>()
+            .add_systems(Update, (pause_menu_toggle_check, pause_menu_interaction));
+
+        app.world.resource_mut::<NextState<GameState>>().set(GameState::Running);
+        app.update();
+
+        app
+    }
+
+    fn game_state(app: &App) -> GameState {
+        app.world.resource::<State<GameState>>().get().clone()
+    }
+
+    #[test]
+    fn escape_opens_the_pause_menu_and_resume_closes_it() {
+        let mut app = pause_menu_app();
+
+        // Escape opens the menu, and pauses the simulation on the next frame.
+
+        app.world.resource_mut::<Input<KeyCode>>().press(KeyCode::Escape);
+        app.update();
+        app.world.resource_mut::<Input<KeyCode>>().clear();
+        app.update();
+
+        let prior = app.world.query::<&PauseMenu>().single(&app.world).prior.clone();
+
+        assert_eq!(prior, GameState::Running);
+        assert_eq!(game_state(&app), GameState::SimPaused);
+
+        // Clicking Resume closes it, and restores the prior state.
+
+        let resume = app
+            .world
+            .query::<(Entity, &PauseMenuButton)>()
+            .iter(&app.world)
+            .find(|(_, button)| **button == PauseMenuButton::Resume)
+            .map(|(entity, _)| entity)
+            .unwrap();
+
+        *app.world.get_mut::<Interaction>(resume).unwrap() = Interaction::Pressed;
+        app.update();
+        app.update();
+
+        assert_eq!(app.world.query::<&PauseMenu>().iter(&app.world).count(), 0);
+        assert_eq!(game_state(&app), GameState::Running);
+    }
+}
@@ -1,9 +1,20 @@
-use super::shared::Player;
-use crate::game::shared::{
-    constants::{C, DAYS_PER_SECOND_UOM, G},
-    types::{Clock, GameItem, GravitationalGamma, Mass, Position, Velocity, VelocityGamma},
+use super::shared::{Fuel, Player};
+use crate::game::{
+    levels::DevMode,
+    shared::{
+        constants::C,
+        helpers::{
+            ease_toward, format_time, format_velocity, gravitational_gamma_breakdown, gravitational_gamma_from_terms,
+            gravitational_gamma_in_frame, is_bound_orbit, total_energy,
+        },
+        types::{
+            Action, Clock, ClockDisplayMode, GameItem, GravFrame, GravitationalGamma, GravitySource, HudConfig, InfluenceRadius,
+            KeyBindings, LaunchOrigin, Mass, PhysicsConfig, Position, TimeCompression, UnitSystem, Velocity, VelocityGamma,
+        },
+    },
 };
 use bevy::prelude::*;
+use uom::si::{f64::Velocity as UomVelocity, velocity::meter_per_second};
 
 // Components / bundles.
 
@@ -17,36 +28,113 @@ pub struct PlayerClockBundle {
     pub clock_text: TextBundle,
 }
 
+#[derive(Component, Default)]
+pub struct HudVelocityComponents;
+
+#[derive(Component, Default)]
+pub struct GammaBreakdownText;
+
 // Startup systems.
 
 pub fn spawn_player_clock(commands: &mut Commands, asset_server: &Res<AssetServer>) {
-    let clock_text = TextBundle::from_section(
-        "t_p = 00.00 γ_v = 1.00 γ_g = 1.00 v_p = 00.00",
+    let style = TextStyle {
+        font_size: 40.0,
+        font: asset_server.load("fonts/HackNerdFontMono-Regular.ttf"),
+        ..Default::default()
+    };
+
+    let clock_text = TextBundle::from_sections([
+        TextSection::new("t_p = 00.00 γ_v = 1.00 γ_g = 1.00 fuel = 100", style.clone()),
+        TextSection::new("\norbit = BOUND", style.clone()),
+        TextSection::new("\nE_tot = 0.00e0 J/kg", style),
+    ])
+    .with_style(Style {
+        position_type: PositionType::Absolute,
+        top: Val::Px(10.0),
+        left: Val::Px(10.0),
+        ..Default::default()
+    });
+
+    commands.spawn(PlayerClockBundle { clock_text, ..Default::default() });
+
+    // The velocity components sit just below the clock panel.
+
+    let velocity_text = TextBundle::from_section(
+        "v_x = 0 km/s v_y = 0 km/s",
         TextStyle {
-            font_size: 40.0,
+            font_size: 30.0,
             font: asset_server.load("fonts/HackNerdFontMono-Regular.ttf"),
             ..Default::default()
         },
     )
     .with_style(Style {
         position_type: PositionType::Absolute,
-        top: Val::Px(10.0),
+        top: Val::Px(170.0),
         left: Val::Px(10.0),
         ..Default::default()
     });
 
-    commands.spawn(PlayerClockBundle { clock_text, ..Default::default() });
+    commands.spawn((GameItem, HudVelocityComponents, velocity_text));
+}
+
+// Helpers.
+
+/// Appends one line per term, with the gamma each mass would give alone, and their sum.
+fn push_gamma_terms(breakdown: &mut String, label: &str, terms: &[(Entity, f64)]) {
+    breakdown.push_str(&format!("{}:\n", label));
+
+    for (entity, term) in terms.iter() {
+        breakdown.push_str(&format!(
+            "  {:?}: 2GM/c²r = {:.3e} (alone γ = {:.6})\n",
+            entity,
+            term,
+            gravitational_gamma_from_terms(&[(*entity, *term)])
+        ));
+    }
+
+    breakdown.push_str(&format!("  Σ = {:.3e}\n", terms.iter().map(|(_, term)| term).sum::<f64>()));
+}
+
+/// The pair of values to display: the true ones, or ones eased toward them when the HUD is smoothed.
+fn ease_hud_values(displayed: &mut Option<(f64, f64)>, target: (f64, f64), hud_config: &HudConfig, dt: f64) -> (f64, f64) {
+    let next = match (*displayed, hud_config.smooth) {
+        (Some((a, b)), true) => (
+            ease_toward(a, target.0, hud_config.smoothing_rate, dt),
+            ease_toward(b, target.1, hud_config.smoothing_rate, dt),
+        ),
+        _ => target,
+    };
+
+    *displayed = Some(next);
+
+    next
 }
 
 // Systems.
 
+pub fn grav_frame_check(keyboard_input: Res<Input<KeyCode>>, key_bindings: Res<KeyBindings>, mut grav_frame: ResMut<GravFrame>) {
+    if !key_bindings.just_pressed(&keyboard_input, Action::ToggleGravFrame) {
+        return;
+    }
+
+    *grav_frame = match *grav_frame {
+        GravFrame::AtInfinity => GravFrame::Local,
+        GravFrame::Local => GravFrame::AtInfinity,
+    };
+}
+
+#[allow(clippy::too_many_arguments)]
 pub fn player_clock_update(
     mut query: Query<(&mut Clock, &mut VelocityGamma, &mut GravitationalGamma), With<Player>>,
     player_query: Query<(Entity, &Position, &Velocity), With<Player>>,
-    masses: Query<(Entity, &Position, &Mass)>,
-    time: Res<Time>,
+    masses: Query<(Entity, &Position, &Mass, Option<&InfluenceRadius>), With<GravitySource>>,
+    fixed_time: Res<FixedTime>,
+    time_compression: Res<TimeCompression>,
+    physics_config: Res<PhysicsConfig>,
+    grav_frame: Res<GravFrame>,
+    launch_origin: Res<LaunchOrigin>,
 ) {
-    let time_elapsed = *DAYS_PER_SECOND_UOM * time.delta_seconds() as f64;
+    let time_elapsed = time_compression.time_elapsed(fixed_time.period.as_secs_f64());
 
     let (mut clock, mut velocity_gamma, mut gravitational_gamma) = query.single_mut();
     let (player_entity, player_position, player_velocity) = player_query.single();
@@ -59,41 +147,205 @@ pub fn player_clock_update(
 
     // Compute gravitational gamma.
 
-    let mut total_graviational_gamma = 1.0f64;
+    let masses: Vec<_> = masses
+        .iter()
+        .map(|(entity, position, mass, influence_radius)| (entity, *position, *mass, influence_radius.copied()))
+        .collect();
 
-    for (other_entity, other_position, other_mass) in masses.iter() {
-        if player_entity == other_entity {
-            continue;
-        }
+    gravitational_gamma.value = gravitational_gamma_in_frame(
+        *grav_frame,
+        player_entity,
+        player_position,
+        &launch_origin.0,
+        &masses,
+        physics_config.softening,
+    );
 
-        let delta_x = player_position.x - other_position.x;
-        let delta_y = player_position.y - other_position.y;
-        let distance_squared = delta_x * delta_x + delta_y * delta_y;
-        let distance = distance_squared.sqrt();
+    clock.value += time_elapsed / velocity_gamma.value / gravitational_gamma.value;
+}
 
-        let mut gravitational_factor = 1.0 - (2.0 * *G * other_mass.value / (*C * *C * distance)).value;
+#[allow(clippy::too_many_arguments)]
+pub fn player_clock_text_update(
+    mut query: Query<(&mut Text, &Clock, &VelocityGamma, &GravitationalGamma), With<Player>>,
+    fuel_query: Query<&Fuel, With<Player>>,
+    clock_display_mode: Res<ClockDisplayMode>,
+    grav_frame: Res<GravFrame>,
+    unit_system: Res<UnitSystem>,
+    hud_config: Res<HudConfig>,
+    time: Res<Time>,
+    mut displayed_gammas: Local<Option<(f64, f64)>>,
+) {
+    let (mut text, clock, velocity_gamma, gravitational_gamma) = query.single_mut();
+    let fuel = fuel_query.single();
 
-        if gravitational_factor <= 0.0001 {
-            gravitational_factor = 0.0001;
-        }
+    let (velocity_gamma, gravitational_gamma) = ease_hud_values(
+        &mut displayed_gammas,
+        (velocity_gamma.value, gravitational_gamma.value),
+        &hud_config,
+        time.delta_seconds_f64(),
+    );
 
-        let gravitational_gamma = 1.0 / gravitational_factor.sqrt();
+    // In difference mode, the observer panel shows the gap, so the player clock is left out.
 
-        total_graviational_gamma *= gravitational_gamma;
-    }
+    let clock_readout = match *clock_display_mode {
+        ClockDisplayMode::Both => format!("t_p = {} ", format_time(clock.value, *unit_system)),
+        ClockDisplayMode::Difference => String::new(),
+    };
+
+    text.sections[0].value = format!(
+        "{}γ_v = {:2.2} {} = {:2.2} fuel = {:3.0}",
+        clock_readout,
+        velocity_gamma,
+        grav_frame.label(),
+        gravitational_gamma,
+        fuel.value
+    );
+}
+
+pub fn player_orbit_text_update(
+    mut query: Query<&mut Text, With<Player>>,
+    player_query: Query<(&Position, &Velocity), With<Player>>,
+    masses: Query<(Entity, &Position, &Mass, Option<&InfluenceRadius>), With<GravitySource>>,
+) {
+    let mut text = query.single_mut();
+    let (player_position, player_velocity) = player_query.single();
 
-    gravitational_gamma.value = total_graviational_gamma;
+    let masses: Vec<_> = masses
+        .iter()
+        .map(|(entity, position, mass, influence_radius)| (entity, *position, *mass, influence_radius.copied()))
+        .collect();
+    let orbit = if is_bound_orbit(player_velocity.scalar(), player_position, &masses) {
+        "BOUND"
+    } else {
+        "ESCAPE"
+    };
 
-    clock.value += time_elapsed / velocity_gamma.value / total_graviational_gamma;
+    text.sections[1].value = format!("\norbit = {}", orbit);
 }
 
-pub fn player_clock_text_update(mut query: Query<(&mut Text, &Clock, &VelocityGamma, &GravitationalGamma), With<Player>>) {
-    let (mut text, clock, velocity_gamma, gravitational_gamma) = query.single_mut();
+pub fn player_energy_text_update(
+    mut query: Query<&mut Text, With<Player>>,
+    player_query: Query<(&Position, &Velocity), With<Player>>,
+    masses: Query<(Entity, &Position, &Mass, Option<&InfluenceRadius>), With<GravitySource>>,
+) {
+    let mut text = query.single_mut();
+    let (player_position, player_velocity) = player_query.single();
 
-    let days = clock.value.value / 24.0 / 3600.0;
+    let masses: Vec<_> = masses
+        .iter()
+        .map(|(entity, position, mass, influence_radius)| (entity, *position, *mass, influence_radius.copied()))
+        .collect();
+
+    text.sections[2].value = format!("\nE_tot = {:.2e} J/kg", total_energy(player_velocity, player_position, &masses));
+}
+
+pub fn player_velocity_text_update(
+    mut query: Query<&mut Text, With<HudVelocityComponents>>,
+    player_query: Query<&Velocity, With<Player>>,
+    unit_system: Res<UnitSystem>,
+    hud_config: Res<HudConfig>,
+    time: Res<Time>,
+    mut displayed_velocity: Local<Option<(f64, f64)>>,
+) {
+    let mut text = query.single_mut();
+    let velocity = player_query.single();
+
+    let (x, y) = ease_hud_values(
+        &mut displayed_velocity,
+        (velocity.x.value, velocity.y.value),
+        &hud_config,
+        time.delta_seconds_f64(),
+    );
 
     text.sections[0].value = format!(
-        "t_p = {:2.2} γ_v = {:2.2} γ_g = {:2.2}",
-        days, velocity_gamma.value, gravitational_gamma.value
+        "v_x = {} v_y = {}",
+        format_velocity(UomVelocity::new::<meter_per_second>(x), *unit_system),
+        format_velocity(UomVelocity::new::<meter_per_second>(y), *unit_system)
+    );
+}
+
+pub fn gamma_breakdown_toggle_check(keyboard_input: Res<Input<KeyCode>>, key_bindings: Res<KeyBindings>, mut dev_mode: ResMut<DevMode>) {
+    if dev_mode.enabled && key_bindings.just_pressed(&keyboard_input, Action::ToggleGammaBreakdown) {
+        dev_mode.gamma_breakdown = !dev_mode.gamma_breakdown;
+    }
+}
+
+/// Lists each mass's term in the player's gravitational gamma, alongside the velocity gamma, for debugging.
+#[allow(clippy::too_many_arguments)]
+pub fn gamma_breakdown_text_update(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    dev_mode: Res<DevMode>,
+    mut text_query: Query<(Entity, &mut Text), With<GammaBreakdownText>>,
+    player_query: Query<(Entity, &Position), With<Player>>,
+    velocity_gamma_query: Query<&VelocityGamma, With<Player>>,
+    masses: Query<(Entity, &Position, &Mass, Option<&InfluenceRadius>), With<GravitySource>>,
+    physics_config: Res<PhysicsConfig>,
+    grav_frame: Res<GravFrame>,
+    launch_origin: Res<LaunchOrigin>,
+) {
+    if !dev_mode.enabled || !dev_mode.gamma_breakdown {
+        for (entity, _) in text_query.iter() {
+            commands.entity(entity).despawn_recursive();
+        }
+
+        return;
+    }
+
+    let (Ok((player_entity, player_position)), Ok(velocity_gamma)) = (player_query.get_single(), velocity_gamma_query.get_single()) else {
+        return;
+    };
+
+    let masses: Vec<_> = masses
+        .iter()
+        .map(|(entity, position, mass, influence_radius)| (entity, *position, *mass, influence_radius.copied()))
+        .collect();
+
+    // Rebuild the total from the listed terms, in the frame the HUD shows, so the two always agree.
+
+    let (player_terms, observer_terms) = gravitational_gamma_breakdown(
+        *grav_frame,
+        player_entity,
+        player_position,
+        &launch_origin.0,
+        &masses,
+        physics_config.softening,
+    );
+    let gravitational_gamma = gravitational_gamma_from_terms(&player_terms) / gravitational_gamma_from_terms(&observer_terms);
+
+    let mut breakdown = format!(
+        "γ_v = {:.6}\n{} = {:.6}\n",
+        velocity_gamma.value,
+        grav_frame.label(),
+        gravitational_gamma
     );
+
+    push_gamma_terms(&mut breakdown, "player", &player_terms);
+
+    if *grav_frame == GravFrame::Local {
+        push_gamma_terms(&mut breakdown, "observer", &observer_terms);
+    }
+
+    match text_query.get_single_mut() {
+        Ok((_, mut text)) => text.sections[0].value = breakdown,
+        Err(_) => {
+            let text = TextBundle::from_section(
+                breakdown,
+                TextStyle {
+                    font_size: 20.0,
+                    font: asset_server.load("fonts/HackNerdFontMono-Regular.ttf"),
+                    ..Default::default()
+                },
+            )
+            .with_style(Style {
+                position_type: PositionType::Absolute,
+                top: Val::Px(210.0),
+                left: Val::Px(10.0),
+                ..Default::default()
+            })
+            .with_background_color(Color::rgba(0.0, 0.0, 0.0, 0.6));
+
+            commands.spawn((GameItem, GammaBreakdownText, text));
+        }
+    }
 }
@@ -1,13 +1,26 @@
-use super::shared::Player;
+use super::shared::{Fuel, Player};
 use crate::{
-    game::shared::{
-        constants::MAX_PLAYER_LAUNCH_VELOCITY,
-        types::{GameItem, Position, Radius, RocketSprite, Velocity},
+    game::{
+        launch_window::{is_launch_allowed, AimTimer, LaunchWindow},
+        levels::CurrentLevel,
+        shared::{
+            constants::{
+                AIM_POWER_PER_SECOND, AIM_RADIANS_PER_SECOND, C, FUEL_BURN_PER_SECOND, MAX_LAUNCH_VELOCITY_FRACTION_OF_C,
+                MAX_VELOCITY_FRACTION_OF_C, THRUST_VELOCITY_PER_FUEL, TRAJECTORY_PREVIEW_STEPS, TRAJECTORY_PREVIEW_STEP_SECONDS,
+            },
+            helpers::{contraction_scale, doppler_color, get_translation_from_position, map_power, predict_trajectory},
+            types::{
+                Action, GameItem, GravityAffected, GravitySource, InfluenceRadius, KeyBindings, Mass, PhysicsConfig, Position, PowerCurve,
+                Radius, RocketSprite, TimeCompression, Velocity, VisualsConfig,
+            },
+        },
     },
-    shared::{state::GameState, SCREEN_HEIGHT_PX, SCREEN_WIDTH_PX},
+    shared::{state::GameState, SCREEN_WIDTH_PX},
 };
 use bevy::{prelude::*, window::PrimaryWindow};
 use glam::DVec2;
+use serde::{Deserialize, Serialize};
+use uom::si::f64::Velocity as UomVelocity;
 
 // Components / bundles.
 
@@ -15,20 +28,133 @@ use glam::DVec2;
 pub struct PlayerSpriteBundle {
     pub item: GameItem,
     pub player: Player,
+    pub gravity_affected: GravityAffected,
     pub position: Position,
     pub radius: Radius,
     pub velocity: Velocity,
+    pub fuel: Fuel,
     pub sprite_type: RocketSprite,
     pub sprite: SpriteBundle,
 }
 
+#[derive(Resource, Default)]
+pub struct ScriptedLaunch {
+    pub angle: f64,
+    pub power: f64,
+    pub delay_frames: u32,
+    /// Whether this level's launch has already fired; loading another level rearms it.
+    pub fired: bool,
+}
+
+impl ScriptedLaunch {
+    /// The environment variable that scripts the launch, as `angle,power[,delay_frames]`, e.g., for demos and benchmarks.
+    pub const ENV_VAR: &'static str = "RELATIVITY_SCRIPTED_LAUNCH";
+
+    pub fn from_env() -> Option<Self> {
+        Self::parse(&std::env::var(Self::ENV_VAR).ok()?)
+    }
+
+    /// Parses `angle,power[,delay_frames]`, with the angle in radians and the power in `[0, 1]`.
+    pub fn parse(value: &str) -> Option<Self> {
+        let mut parts = value.split(',').map(str::trim);
+
+        let angle = parts.next()?.parse().ok()?;
+        let power = parts.next()?.parse().ok()?;
+        let delay_frames = match parts.next() {
+            Some(delay_frames) => delay_frames.parse().ok()?,
+            None => 0,
+        };
+
+        if parts.next().is_some() {
+            return None;
+        }
+
+        Some(Self { angle, power, delay_frames, fired: false })
+    }
+}
+
+#[derive(Resource)]
+pub struct KeyboardAim {
+    pub angle: f64,
+    pub power: f64,
+}
+
+impl Default for KeyboardAim {
+    fn default() -> Self {
+        Self { angle: 0.0, power: 0.5 }
+    }
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum AimInput {
+    #[default]
+    Keyboard,
+    Mouse,
+}
+
+/// The launch the player is lining up, from whichever input they last aimed with, so previews show the shot they're
+/// about to fire.
+#[derive(Resource, Default)]
+pub struct LaunchAim {
+    pub input: AimInput,
+    pub velocity: Velocity,
+}
+
+/// The rocket sprites the player can pick from in the menu.
+pub const SHIP_SKINS: [&str; 4] = [
+    "sprites/space/Rockets/spaceRockets_001.png",
+    "sprites/space/Rockets/spaceRockets_002.png",
+    "sprites/space/Rockets/spaceRockets_003.png",
+    "sprites/space/Rockets/spaceRockets_004.png",
+];
+
+/// The player's rocket sprite, applied in every level.
+#[derive(Resource, Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct ShipSkin {
+    pub sprite: String,
+}
+
+impl Default for ShipSkin {
+    fn default() -> Self {
+        Self { sprite: SHIP_SKINS[2].to_string() }
+    }
+}
+
+#[derive(Resource, Clone, Debug, PartialEq)]
+pub struct LaunchConfig {
+    /// Launch speed at full power, as a fraction of c; see `max_velocity` for the clamped speed.
+    pub max_velocity_fraction: f64,
+    pub power_curve: PowerCurve,
+}
+
+impl Default for LaunchConfig {
+    fn default() -> Self {
+        Self {
+            max_velocity_fraction: MAX_LAUNCH_VELOCITY_FRACTION_OF_C,
+            power_curve: PowerCurve::default(),
+        }
+    }
+}
+
+impl LaunchConfig {
+    /// Launch speed at full power, kept below c so the player's gamma stays finite.
+    pub fn max_velocity(&self) -> UomVelocity {
+        *C * self.max_velocity_fraction.clamp(0.0, MAX_VELOCITY_FRACTION_OF_C)
+    }
+}
+
 // Systems.
 
+#[allow(clippy::too_many_arguments)]
 pub fn player_launch(
     mouse_input: Res<Input<MouseButton>>,
     mut player_velocity_query: Query<(&Transform, &mut Velocity), With<Player>>,
     window_query: Query<&Window, With<PrimaryWindow>>,
+    camera_query: Query<(&Camera, &GlobalTransform)>,
     mut state: ResMut<NextState<GameState>>,
+    launch_config: Res<LaunchConfig>,
+    launch_window: Option<Res<LaunchWindow>>,
+    aim_timer: Res<AimTimer>,
 ) {
     let Ok((player_transform, mut player_velocity)) = player_velocity_query.get_single_mut() else {
         return;
@@ -38,19 +164,407 @@ pub fn player_launch(
         return;
     }
 
+    if !is_launch_allowed(launch_window.as_deref(), &aim_timer) {
+        return;
+    }
+
     let window = window_query.get_single().unwrap();
-    let cursor_position = window.cursor_position().unwrap();
-    let cursor_transform = DVec2::new(cursor_position.x as f64, SCREEN_HEIGHT_PX - cursor_position.y as f64);
 
-    let launch_vector = DVec2::new(
-        cursor_transform.x - player_transform.translation.x as f64,
-        cursor_transform.y - player_transform.translation.y as f64,
+    let Ok(camera) = camera_query.get_single() else {
+        return;
+    };
+
+    let Some(launch_velocity) = cursor_launch_velocity(player_transform, window, camera, &launch_config) else {
+        return;
+    };
+
+    *player_velocity = launch_velocity;
+
+    state.set(GameState::Running);
+}
+
+#[allow(clippy::too_many_arguments)]
+pub fn player_launch_preview(
+    mut gizmos: Gizmos,
+    player_query: Query<(Entity, &Transform, &Position), With<Player>>,
+    masses: Query<(Entity, &Position, &Mass, Option<&InfluenceRadius>), With<GravitySource>>,
+    window_query: Query<&Window, With<PrimaryWindow>>,
+    camera_query: Query<(&Camera, &GlobalTransform)>,
+    time_compression: Res<TimeCompression>,
+    physics_config: Res<PhysicsConfig>,
+    launch_config: Res<LaunchConfig>,
+) {
+    let Ok((player_entity, player_transform, player_position)) = player_query.get_single() else {
+        return;
+    };
+
+    let Ok(window) = window_query.get_single() else {
+        return;
+    };
+
+    let Ok(camera) = camera_query.get_single() else {
+        return;
+    };
+
+    let Some(launch_velocity) = cursor_launch_velocity(player_transform, window, camera, &launch_config) else {
+        return;
+    };
+
+    let masses: Vec<_> = masses
+        .iter()
+        .map(|(entity, position, mass, influence_radius)| (entity, *position, *mass, influence_radius.copied()))
+        .collect();
+    let time_step = time_compression.time_elapsed(TRAJECTORY_PREVIEW_STEP_SECONDS);
+
+    let trajectory = predict_trajectory(
+        player_entity,
+        *player_position,
+        launch_velocity,
+        &masses,
+        time_step,
+        physics_config.softening,
+        TRAJECTORY_PREVIEW_STEPS,
     );
-    let launch_direction = launch_vector.normalize();
-    let launch_power = f64::min(0.8 * SCREEN_WIDTH_PX, launch_vector.length()) / (0.8 * SCREEN_WIDTH_PX);
 
-    player_velocity.x = *MAX_PLAYER_LAUNCH_VELOCITY * launch_power * launch_direction.x;
-    player_velocity.y = *MAX_PLAYER_LAUNCH_VELOCITY * launch_power * launch_direction.y;
+    // Draw every other segment for a dashed line.
+
+    for segment in trajectory.windows(2).step_by(2) {
+        let start = get_translation_from_position(&segment[0]).truncate();
+        let end = get_translation_from_position(&segment[1]).truncate();
+
+        gizmos.line_2d(start, end, Color::rgba(1.0, 1.0, 1.0, 0.5));
+    }
+}
+
+pub fn player_doppler_tint_update(mut query: Query<(&mut Sprite, &Velocity), With<Player>>) {
+    for (mut sprite, velocity) in query.iter_mut() {
+        let speed = velocity.scalar();
+
+        if speed.value == 0.0 {
+            sprite.color = Color::WHITE;
+            continue;
+        }
+
+        // The observer sits at the top of the screen.
+
+        let beta = (speed / *C).value;
+        let cos_theta = (velocity.y / speed).value;
+
+        sprite.color = doppler_color(Color::WHITE, beta, cos_theta);
+    }
+}
+
+#[allow(clippy::type_complexity)]
+pub fn player_length_contraction_update(
+    mut query: Query<(&mut Transform, &Velocity), (With<Player>, With<RocketSprite>)>,
+    visuals_config: Res<VisualsConfig>,
+) {
+    if !visuals_config.length_contraction {
+        return;
+    }
+
+    for (mut transform, velocity) in query.iter_mut() {
+        // The sprite is rotated to face its velocity, so motion is along its local y axis.
+
+        let beta = (velocity.scalar() / *C).value;
+        let scale = contraction_scale(beta, Vec2::Y);
+
+        transform.scale.x *= scale.x;
+        transform.scale.y *= scale.y;
+    }
+}
+
+pub fn player_thrust_update(
+    keyboard_input: Res<Input<KeyCode>>,
+    key_bindings: Res<KeyBindings>,
+    mut query: Query<(&mut Velocity, &mut Fuel), With<Player>>,
+    fixed_time: Res<FixedTime>,
+) {
+    let Ok((mut velocity, mut fuel)) = query.get_single_mut() else {
+        return;
+    };
+
+    if fuel.value <= 0.0 {
+        return;
+    }
+
+    let mut direction = DVec2::ZERO;
+
+    if key_bindings.pressed(&keyboard_input, Action::ThrustUp) {
+        direction.y += 1.0;
+    }
+
+    if key_bindings.pressed(&keyboard_input, Action::ThrustDown) {
+        direction.y -= 1.0;
+    }
+
+    if key_bindings.pressed(&keyboard_input, Action::ThrustLeft) {
+        direction.x -= 1.0;
+    }
+
+    if key_bindings.pressed(&keyboard_input, Action::ThrustRight) {
+        direction.x += 1.0;
+    }
+
+    if direction == DVec2::ZERO {
+        return;
+    }
+
+    let direction = direction.normalize();
+    let burn = f64::min(fuel.value, FUEL_BURN_PER_SECOND * fixed_time.period.as_secs_f64());
+
+    velocity.x += *THRUST_VELOCITY_PER_FUEL * burn * direction.x;
+    velocity.y += *THRUST_VELOCITY_PER_FUEL * burn * direction.y;
+
+    fuel.value -= burn;
+}
+
+pub fn player_scripted_launch(
+    scripted_launch: Option<ResMut<ScriptedLaunch>>,
+    mut player_velocity_query: Query<&mut Velocity, With<Player>>,
+    mut state: ResMut<NextState<GameState>>,
+    mut frames_waited: Local<u32>,
+    launch_config: Res<LaunchConfig>,
+    current_level: Res<CurrentLevel>,
+) {
+    let Some(mut scripted_launch) = scripted_launch else {
+        return;
+    };
+
+    // Fire once per level load, rather than every time the state returns to aiming, e.g., after a restart or the
+    // pause menu.
+
+    if current_level.is_changed() {
+        scripted_launch.fired = false;
+        *frames_waited = 0;
+    }
+
+    if scripted_launch.fired {
+        return;
+    }
+
+    let Ok(mut player_velocity) = player_velocity_query.get_single_mut() else {
+        return;
+    };
+
+    if *frames_waited < scripted_launch.delay_frames {
+        *frames_waited += 1;
+        return;
+    }
+
+    *frames_waited = 0;
+    scripted_launch.fired = true;
+
+    *player_velocity = launch_velocity(scripted_launch.angle, scripted_launch.power, &launch_config);
 
     state.set(GameState::Running);
 }
+
+#[allow(clippy::too_many_arguments)]
+pub fn player_keyboard_launch(
+    mut gizmos: Gizmos,
+    keyboard_input: Res<Input<KeyCode>>,
+    key_bindings: Res<KeyBindings>,
+    mut keyboard_aim: ResMut<KeyboardAim>,
+    mut player_velocity_query: Query<(&Transform, &mut Velocity), With<Player>>,
+    mut state: ResMut<NextState<GameState>>,
+    time: Res<Time>,
+    launch_config: Res<LaunchConfig>,
+    launch_window: Option<Res<LaunchWindow>>,
+    aim_timer: Res<AimTimer>,
+) {
+    let Ok((player_transform, mut player_velocity)) = player_velocity_query.get_single_mut() else {
+        return;
+    };
+
+    let delta_seconds = time.delta_seconds() as f64;
+
+    if key_bindings.pressed(&keyboard_input, Action::AimLeft) {
+        keyboard_aim.angle += AIM_RADIANS_PER_SECOND * delta_seconds;
+    }
+
+    if key_bindings.pressed(&keyboard_input, Action::AimRight) {
+        keyboard_aim.angle -= AIM_RADIANS_PER_SECOND * delta_seconds;
+    }
+
+    if key_bindings.pressed(&keyboard_input, Action::PowerUp) {
+        keyboard_aim.power = (keyboard_aim.power + AIM_POWER_PER_SECOND * delta_seconds).min(1.0);
+    }
+
+    if key_bindings.pressed(&keyboard_input, Action::PowerDown) {
+        keyboard_aim.power = (keyboard_aim.power - AIM_POWER_PER_SECOND * delta_seconds).max(0.0);
+    }
+
+    // Show the aim as a line whose length is the effective power, after the power curve.
+
+    let start = player_transform.translation.truncate();
+    let direction = Vec2::new(keyboard_aim.angle.cos() as f32, keyboard_aim.angle.sin() as f32);
+    let length = 0.8 * SCREEN_WIDTH_PX as f32 * map_power(launch_config.power_curve, keyboard_aim.power) as f32;
+
+    gizmos.line_2d(start, start + length * direction, Color::rgba(1.0, 1.0, 0.0, 0.5));
+
+    if !key_bindings.just_pressed(&keyboard_input, Action::Launch) {
+        return;
+    }
+
+    if !is_launch_allowed(launch_window.as_deref(), &aim_timer) {
+        return;
+    }
+
+    *player_velocity = launch_velocity(keyboard_aim.angle, keyboard_aim.power, &launch_config);
+
+    state.set(GameState::Running);
+}
+
+#[allow(clippy::too_many_arguments)]
+pub fn launch_aim_update(
+    mut cursor_moved_events: EventReader<CursorMoved>,
+    keyboard_input: Res<Input<KeyCode>>,
+    key_bindings: Res<KeyBindings>,
+    keyboard_aim: Res<KeyboardAim>,
+    player_query: Query<&Transform, With<Player>>,
+    window_query: Query<&Window, With<PrimaryWindow>>,
+    camera_query: Query<(&Camera, &GlobalTransform)>,
+    launch_config: Res<LaunchConfig>,
+    mut launch_aim: ResMut<LaunchAim>,
+) {
+    // Moving the mouse hands the aim to the cursor; pressing an aim key hands it back to the keyboard.
+
+    if cursor_moved_events.iter().count() > 0 {
+        launch_aim.input = AimInput::Mouse;
+    }
+
+    let is_keyboard_aiming = [Action::AimLeft, Action::AimRight, Action::PowerUp, Action::PowerDown]
+        .into_iter()
+        .any(|action| key_bindings.pressed(&keyboard_input, action));
+
+    if is_keyboard_aiming {
+        launch_aim.input = AimInput::Keyboard;
+    }
+
+    let cursor_velocity = match (player_query.get_single(), window_query.get_single(), camera_query.get_single()) {
+        (Ok(player_transform), Ok(window), Ok(camera)) => cursor_launch_velocity(player_transform, window, camera, &launch_config),
+        _ => None,
+    };
+
+    // Fall back to the keyboard's aim while the cursor is off the window.
+
+    launch_aim.velocity = match (launch_aim.input, cursor_velocity) {
+        (AimInput::Mouse, Some(cursor_velocity)) => cursor_velocity,
+        _ => launch_velocity(keyboard_aim.angle, keyboard_aim.power, &launch_config),
+    };
+}
+
+// Helpers.
+
+pub fn launch_velocity(angle: f64, power: f64, launch_config: &LaunchConfig) -> Velocity {
+    let max_velocity = launch_config.max_velocity();
+    let power = map_power(launch_config.power_curve, power);
+
+    Velocity {
+        x: max_velocity * power * angle.cos(),
+        y: max_velocity * power * angle.sin(),
+    }
+}
+
+pub fn cursor_launch_velocity(
+    player_transform: &Transform,
+    window: &Window,
+    camera: (&Camera, &GlobalTransform),
+    launch_config: &LaunchConfig,
+) -> Option<Velocity> {
+    let (camera, camera_transform) = camera;
+
+    // Go through the camera, since it may have followed or zoomed away from the screen-sized view.
+
+    let cursor_position = camera.viewport_to_world_2d(camera_transform, window.cursor_position()?)?;
+
+    let launch_vector = (cursor_position - player_transform.translation.truncate()).as_dvec2();
+    let launch_angle = launch_vector.y.atan2(launch_vector.x);
+    let launch_power = f64::min(0.8 * SCREEN_WIDTH_PX, launch_vector.length()) / (0.8 * SCREEN_WIDTH_PX);
+
+    Some(launch_velocity(launch_angle, launch_power, launch_config))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn scripted_launch_parses_with_and_without_a_delay() {
+        let scripted_launch = ScriptedLaunch::parse("0.8, 0.5, 30").unwrap();
+
+        assert_eq!(
+            (scripted_launch.angle, scripted_launch.power, scripted_launch.delay_frames),
+            (0.8, 0.5, 30)
+        );
+        assert_eq!(ScriptedLaunch::parse("0.8,0.5").unwrap().delay_frames, 0);
+        assert!(ScriptedLaunch::parse("0.8").is_none());
+        assert!(ScriptedLaunch::parse("0.8,0.5,30,1").is_none());
+    }
+
+    #[test]
+    fn scripted_launch_fires_after_the_delay_without_input() {
+        let mut app = App::new();
+
+        app.add_plugins(MinimalPlugins)
+            .add_state::<GameState>()
+            .init_resource::<LaunchConfig>()
+            .init_resource::<CurrentLevel>()
+            .insert_resource(ScriptedLaunch {
+                angle: 0.3,
+                power: 0.7,
+                delay_frames: 3,
+                fired: false,
+            })
+            .add_systems(Update, player_scripted_launch.run_if(in_state(GameState::Paused)));
+
+        let player = app.world.spawn((Player, Velocity::default())).id();
+
+        // Still aiming through the delay.
+
+        for _ in 0..3 {
+            app.update();
+        }
+
+        assert_eq!(*app.world.get::<Velocity>(player).unwrap(), Velocity::default());
+        assert_eq!(*app.world.resource::<State<GameState>>().get(), GameState::Paused);
+
+        // Then launched, at the scripted velocity.
+
+        app.update();
+        app.update();
+
+        assert_eq!(
+            *app.world.get::<Velocity>(player).unwrap(),
+            launch_velocity(0.3, 0.7, &LaunchConfig::default())
+        );
+        assert_eq!(*app.world.resource::<State<GameState>>().get(), GameState::Running);
+        assert!(app.world.resource::<ScriptedLaunch>().fired);
+    }
+
+    #[test]
+    fn launch_aim_reflects_the_power_curve() {
+        let aimed_speed = |power_curve: PowerCurve| {
+            let mut app = App::new();
+
+            app.add_event::<CursorMoved>()
+                .init_resource::<Input<KeyCode>>()
+                .init_resource::<KeyBindings>()
+                .insert_resource(KeyboardAim { angle: 0.0, power: 0.5 })
+                .insert_resource(LaunchConfig { power_curve, ..Default::default() })
+                .init_resource::<LaunchAim>()
+                .add_systems(Update, launch_aim_update);
+            app.world.spawn((Player, Transform::default()));
+            app.update();
+
+            (app.world.resource::<LaunchAim>().velocity.scalar() / LaunchConfig::default().max_velocity()).value
+        };
+
+        // Without a window, the aim falls back to the keyboard's, at half power.
+
+        assert!((aimed_speed(PowerCurve::Linear) - 0.5).abs() < 1e-12);
+        assert!((aimed_speed(PowerCurve::Quadratic) - 0.25).abs() < 1e-12);
+        assert!((aimed_speed(PowerCurve::Exponential { k: 2.0 }) - map_power(PowerCurve::Exponential { k: 2.0 }, 0.5)).abs() < 1e-12);
+    }
+}
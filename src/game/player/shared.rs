@@ -1,4 +1,17 @@
 use bevy::prelude::Component;
 
+use crate::game::shared::constants::PLAYER_STARTING_FUEL;
+
 #[derive(Component, Default)]
 pub struct Player;
+
+#[derive(Component)]
+pub struct Fuel {
+    pub value: f64,
+}
+
+impl Default for Fuel {
+    fn default() -> Self {
+        Self { value: PLAYER_STARTING_FUEL }
+    }
+}
@@ -0,0 +1,93 @@
+use bevy::prelude::*;
+
+use crate::shared::{SCREEN_HEIGHT_PX, SCREEN_WIDTH_PX};
+
+use super::{
+    player::shared::Player,
+    shared::{
+        constants::SCREEN_WIDTH_UOM,
+        helpers::{get_translation_from_position, sample_potential_cross_section},
+        types::{Action, GravitySource, InfluenceRadius, KeyBindings, Mass, Position, VisualsConfig},
+    },
+};
+
+const CROSS_SECTION_SAMPLES: usize = 256;
+const CROSS_SECTION_HEIGHT_PX: f32 = 120.0;
+const CROSS_SECTION_MARGIN_PX: f32 = 10.0;
+
+// Systems.
+
+pub fn potential_cross_section_toggle_check(
+    keyboard_input: Res<Input<KeyCode>>,
+    key_bindings: Res<KeyBindings>,
+    mut visuals_config: ResMut<VisualsConfig>,
+) {
+    if key_bindings.just_pressed(&keyboard_input, Action::TogglePotential) {
+        visuals_config.potential_cross_section = !visuals_config.potential_cross_section;
+    }
+}
+
+pub fn potential_cross_section_update(
+    mut gizmos: Gizmos,
+    visuals_config: Res<VisualsConfig>,
+    camera_query: Query<(&Transform, &OrthographicProjection), With<Camera>>,
+    player_query: Query<&Position, With<Player>>,
+    masses: Query<(Entity, &Position, &Mass, Option<&InfluenceRadius>), With<GravitySource>>,
+) {
+    if !visuals_config.potential_cross_section {
+        return;
+    }
+
+    let Ok((camera_transform, projection)) = camera_query.get_single() else {
+        return;
+    };
+
+    let Ok(player_position) = player_query.get_single() else {
+        return;
+    };
+
+    // Sample along the player's row, across the visible width.
+
+    let zoom = projection.scale;
+    let view_width = SCREEN_WIDTH_PX as f32 * zoom;
+    let view_left = camera_transform.translation.x - view_width / 2.0;
+    let view_bottom = camera_transform.translation.y - SCREEN_HEIGHT_PX as f32 * zoom / 2.0;
+    let height = CROSS_SECTION_HEIGHT_PX * zoom;
+
+    let x_start = *SCREEN_WIDTH_UOM * (view_left as f64 / SCREEN_WIDTH_PX);
+    let x_end = x_start + *SCREEN_WIDTH_UOM * zoom as f64;
+
+    let masses: Vec<_> = masses
+        .iter()
+        .map(|(entity, position, mass, influence_radius)| (entity, *position, *mass, influence_radius.copied()))
+        .collect();
+    let samples = sample_potential_cross_section(player_position.y, x_start, x_end, &masses, CROSS_SECTION_SAMPLES);
+
+    // Normalize to the deepest well, so the graph hangs down from its top edge.
+
+    let deepest = samples.iter().map(|(_, potential)| *potential).fold(0.0, f64::min);
+
+    if deepest == 0.0 {
+        return;
+    }
+
+    let top = view_bottom + (CROSS_SECTION_MARGIN_PX + CROSS_SECTION_HEIGHT_PX) * zoom;
+
+    let points: Vec<_> = samples
+        .iter()
+        .map(|(x, potential)| {
+            let x = get_translation_from_position(&Position { x: *x, y: player_position.y }).x;
+            let y = top - height * (potential / deepest) as f32;
+
+            Vec2::new(x, y)
+        })
+        .collect();
+
+    gizmos.line_2d(
+        Vec2::new(view_left, top),
+        Vec2::new(view_left + view_width, top),
+        Color::rgba(1.0, 1.0, 1.0, 0.25),
+    );
+
+    gizmos.linestrip_2d(points, Color::CYAN);
+}
@@ -0,0 +1,165 @@
+use bevy::prelude::*;
+use uom::si::f64::Time as UomTime;
+
+use crate::shared::state::GameState;
+
+use super::{
+    black_hole::BlackHole,
+    destination::{Destination, WaypointProgress},
+    object::Planet,
+    player::{player_sprite::LaunchAim, shared::Player},
+    shared::{
+        constants::{LAUNCH_OUTCOME_STEPS, TRAJECTORY_PREVIEW_STEP_SECONDS},
+        helpers::simulate_launch_outcome,
+        types::{
+            CaptureRadius, CollisionResponse, GravitySource, InfluenceRadius, LaunchOutcome, Mass, PhysicsConfig, Position, Radius,
+            TimeCompression, Velocity,
+        },
+    },
+};
+
+/// How often, in real seconds, the preview of a flight in progress is rerun.
+const PREVIEW_REFRESH_SECONDS: f64 = 0.25;
+
+// Resources.
+
+/// One forward simulation, of the aimed launch while paused or of the flight while running, shared by the ETA, collision
+/// warning, and launch outcome readouts.
+#[derive(Resource, Default)]
+pub struct TrajectoryPreview {
+    /// The player, position, and velocity simulated from; while aiming, the preview is only rerun once these change.
+    pub start: Option<(Entity, Position, Velocity)>,
+    pub time_step: UomTime,
+    /// Real seconds since the simulation was run.
+    pub age: f64,
+    pub path: Vec<Position>,
+    pub outcome: Option<LaunchOutcome>,
+}
+
+impl TrajectoryPreview {
+    /// The rest of the path, skipping the steps a running flight has already flown since the simulation was run.
+    pub fn remaining_path(&self, time_compression: &TimeCompression) -> &[Position] {
+        if self.time_step.value <= 0.0 {
+            return &self.path;
+        }
+
+        let flown = (time_compression.time_elapsed(self.age) / self.time_step).value.floor() as usize;
+
+        self.path.get(flown..).unwrap_or(&[])
+    }
+
+    /// Simulated time until the path reaches its target, if it does; `predict_destination_arrival` from the start, less
+    /// the time flown since, without rerunning the simulation.
+    pub fn arrival(&self, time_compression: &TimeCompression) -> Option<UomTime> {
+        if self.outcome != Some(LaunchOutcome::Hit) {
+            return None;
+        }
+
+        let remaining_steps = self.remaining_path(time_compression).len().saturating_sub(1);
+
+        Some(self.time_step * remaining_steps as f64)
+    }
+}
+
+// Systems.
+
+#[allow(clippy::too_many_arguments)]
+pub fn trajectory_preview_update(
+    mut preview: ResMut<TrajectoryPreview>,
+    time: Res<Time>,
+    game_state: Res<State<GameState>>,
+    player_query: Query<(Entity, &Position, &Velocity, &Radius), With<Player>>,
+    planet_query: Query<(Entity, &Position, &Radius, &CollisionResponse), With<Planet>>,
+    black_hole_query: Query<(Entity, &Position, &Mass), With<BlackHole>>,
+    destination_query: Query<(&Position, &Radius, Option<&CaptureRadius>, &Destination)>,
+    masses: Query<(Entity, &Position, &Mass, Option<&InfluenceRadius>), With<GravitySource>>,
+    launch_aim: Res<LaunchAim>,
+    waypoint_progress: Res<WaypointProgress>,
+    time_compression: Res<TimeCompression>,
+    physics_config: Res<PhysicsConfig>,
+) {
+    let is_aiming = match game_state.get() {
+        GameState::Paused => true,
+        GameState::Running => false,
+        _ => return,
+    };
+
+    let Ok((player_entity, player_position, player_velocity, player_radius)) = player_query.get_single() else {
+        return;
+    };
+
+    preview.age += time.delta_seconds_f64();
+
+    // While aiming, simulate the launch about to be fired, by mouse or keyboard; in flight, the player's actual course.
+
+    let velocity = if is_aiming { launch_aim.velocity } else { *player_velocity };
+    let start = (player_entity, *player_position, velocity);
+
+    let is_stale = if is_aiming {
+        preview.start != Some(start)
+    } else {
+        preview.age >= PREVIEW_REFRESH_SECONDS
+    };
+
+    if !is_stale && !game_state.is_changed() {
+        return;
+    }
+
+    // A launch is headed for the first waypoint; a flight, for the next unvisited one. Either falls back to the final
+    // destination.
+
+    let next_waypoint = Destination::Waypoint {
+        order: if is_aiming { 0 } else { waypoint_progress.next },
+    };
+    let target = destination_query
+        .iter()
+        .find(|(.., destination)| **destination == next_waypoint)
+        .or_else(|| {
+            destination_query
+                .iter()
+                .find(|(.., destination)| **destination == Destination::Final)
+        });
+
+    let Some((destination_position, destination_radius, capture_radius, _)) = target else {
+        return;
+    };
+
+    let capture_radius = CaptureRadius::or_radius(capture_radius, destination_radius);
+
+    let masses: Vec<_> = masses
+        .iter()
+        .map(|(entity, position, mass, influence_radius)| (entity, *position, *mass, influence_radius.copied()))
+        .collect();
+    let bodies: Vec<_> = planet_query
+        .iter()
+        .filter(|(.., collision_response)| **collision_response == CollisionResponse::Fail)
+        .map(|(entity, position, radius, _)| (entity, *position, *radius))
+        .collect();
+    let black_holes: Vec<_> = black_hole_query
+        .iter()
+        .map(|(entity, position, mass)| (entity, *position, *mass))
+        .collect();
+    let time_step = time_compression.time_elapsed(TRAJECTORY_PREVIEW_STEP_SECONDS);
+
+    let (outcome, path) = simulate_launch_outcome(
+        player_entity,
+        *player_position,
+        velocity,
+        player_radius,
+        &masses,
+        &bodies,
+        &black_holes,
+        (destination_position, &capture_radius),
+        time_step,
+        physics_config.softening,
+        LAUNCH_OUTCOME_STEPS,
+    );
+
+    *preview = TrajectoryPreview {
+        start: Some(start),
+        time_step,
+        age: 0.0,
+        path,
+        outcome: Some(outcome),
+    };
+}
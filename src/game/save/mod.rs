@@ -0,0 +1,151 @@
+use std::{io, path::Path};
+
+use bevy::{prelude::*, utils::HashMap};
+use serde::{Deserialize, Serialize};
+
+use crate::shared::{state::GameState, types::Theme};
+
+use super::{
+    audio::AudioConfig,
+    levels::{respawn_level, CurrentLevel},
+    player::player_sprite::ShipSkin,
+    score::BestTimes,
+    shared::types::{Action, ClockDisplayMode, GameItem, GameplayScale, KeyBindings},
+};
+
+const SAVE_PATH: &str = "relativity_save.ron";
+
+// Save state.
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct SaveState {
+    pub current_level: CurrentLevel,
+    pub best_times: HashMap<CurrentLevel, f64>,
+    pub audio_config: AudioConfig,
+    pub clock_display_mode: ClockDisplayMode,
+    #[serde(default)]
+    pub ship_skin: ShipSkin,
+    #[serde(default)]
+    pub theme: Theme,
+}
+
+pub fn save_session(path: impl AsRef<Path>, save_state: &SaveState) -> io::Result<()> {
+    let contents = ron::ser::to_string_pretty(save_state, ron::ser::PrettyConfig::default())
+        .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+
+    std::fs::write(path, contents)
+}
+
+pub fn load_session(path: impl AsRef<Path>) -> io::Result<SaveState> {
+    let contents = std::fs::read_to_string(path)?;
+
+    ron::from_str(&contents).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+}
+
+// Systems.
+
+#[allow(clippy::too_many_arguments)]
+pub fn save_session_check(
+    keyboard_input: Res<Input<KeyCode>>,
+    key_bindings: Res<KeyBindings>,
+    current_level: Res<CurrentLevel>,
+    best_times: Res<BestTimes>,
+    audio_config: Res<AudioConfig>,
+    clock_display_mode: Res<ClockDisplayMode>,
+    ship_skin: Res<ShipSkin>,
+    theme: Res<Theme>,
+) {
+    if !key_bindings.just_pressed(&keyboard_input, Action::Save) {
+        return;
+    }
+
+    let save_state = SaveState {
+        current_level: current_level.clone(),
+        best_times: best_times.seconds.clone(),
+        audio_config: audio_config.clone(),
+        clock_display_mode: *clock_display_mode,
+        ship_skin: ship_skin.clone(),
+        theme: *theme,
+    };
+
+    match save_session(SAVE_PATH, &save_state) {
+        Ok(()) => info!("saved the session to `{}`", SAVE_PATH),
+        Err(e) => warn!("failed to save the session to `{}`: {}", SAVE_PATH, e),
+    }
+}
+
+#[allow(clippy::too_many_arguments)]
+pub fn load_session_check(
+    keyboard_input: Res<Input<KeyCode>>,
+    key_bindings: Res<KeyBindings>,
+    commands: Commands,
+    query: Query<Entity, With<GameItem>>,
+    asset_server: Res<AssetServer>,
+    gameplay_scale: Res<GameplayScale>,
+    mut game_state: ResMut<NextState<GameState>>,
+    mut current_level: ResMut<CurrentLevel>,
+    mut best_times: ResMut<BestTimes>,
+    mut audio_config: ResMut<AudioConfig>,
+    mut clock_display_mode: ResMut<ClockDisplayMode>,
+    mut ship_skin: ResMut<ShipSkin>,
+    mut theme: ResMut<Theme>,
+) {
+    if !key_bindings.just_pressed(&keyboard_input, Action::Load) {
+        return;
+    }
+
+    let save_state = match load_session(SAVE_PATH) {
+        Ok(save_state) => save_state,
+        Err(e) => {
+            warn!("failed to load the session from `{}`: {}", SAVE_PATH, e);
+            return;
+        }
+    };
+
+    *current_level = save_state.current_level;
+    best_times.seconds = save_state.best_times;
+    *audio_config = save_state.audio_config;
+    *clock_display_mode = save_state.clock_display_mode;
+    *ship_skin = save_state.ship_skin;
+    *theme = save_state.theme;
+
+    // The saved level (and skin) may differ from what's on screen, so start it over from scratch.
+
+    respawn_level(
+        commands,
+        asset_server,
+        &ship_skin,
+        &gameplay_scale,
+        &query,
+        &mut game_state,
+        &current_level,
+    );
+
+    info!("loaded the session from `{}`", SAVE_PATH);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn save_session_round_trips_through_load_session() {
+        let path = std::env::temp_dir().join(format!("relativity_save_round_trip_{}.ron", std::process::id()));
+        let save_state = SaveState {
+            current_level: CurrentLevel::Custom("wells".to_string()),
+            best_times: [(CurrentLevel::One, 12.5), (CurrentLevel::Slingshot, 30.25)].into_iter().collect(),
+            audio_config: AudioConfig { volume: 0.25, muted: true },
+            clock_display_mode: ClockDisplayMode::Difference,
+            ship_skin: ShipSkin {
+                sprite: "sprites/custom_ship.png".to_string(),
+            },
+            theme: Theme { background: Color::rgb(0.1, 0.2, 0.3) },
+        };
+
+        save_session(&path, &save_state).unwrap();
+        let loaded = load_session(&path);
+        std::fs::remove_file(&path).unwrap();
+
+        assert_eq!(loaded.unwrap(), save_state);
+    }
+}
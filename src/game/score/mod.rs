@@ -0,0 +1,77 @@
+use bevy::{prelude::*, utils::HashMap};
+
+use super::levels::CurrentLevel;
+
+// Components / bundles / resources.
+
+#[derive(Resource, Default)]
+pub struct LevelTimer {
+    pub seconds: f64,
+    /// Whether the last finished run beat the level's best time.
+    pub is_best: bool,
+}
+
+#[derive(Resource, Default)]
+pub struct BestTimes {
+    pub seconds: HashMap<CurrentLevel, f64>,
+}
+
+impl BestTimes {
+    pub fn record(&mut self, level: &CurrentLevel, seconds: f64) -> bool {
+        match self.seconds.get(level) {
+            Some(best) if *best <= seconds => false,
+            _ => {
+                self.seconds.insert(level.clone(), seconds);
+                true
+            }
+        }
+    }
+}
+
+// Systems.
+
+pub fn level_timer_reset(mut level_timer: ResMut<LevelTimer>) {
+    level_timer.seconds = 0.0;
+}
+
+pub fn level_timer_update(mut level_timer: ResMut<LevelTimer>, time: Res<Time>) {
+    level_timer.seconds += time.delta_seconds() as f64;
+}
+
+pub fn level_timer_finish(mut level_timer: ResMut<LevelTimer>, current_level: Res<CurrentLevel>, mut best_times: ResMut<BestTimes>) {
+    level_timer.is_best = best_times.record(&current_level, level_timer.seconds);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn finish_run(world: &mut World, seconds: f64) -> bool {
+        world.resource_mut::<LevelTimer>().seconds = seconds;
+
+        let mut schedule = Schedule::default();
+        schedule.add_systems(level_timer_finish);
+        schedule.run(world);
+
+        world.resource::<LevelTimer>().is_best
+    }
+
+    #[test]
+    fn level_timer_finish_keeps_the_fastest_run() {
+        let mut world = World::new();
+        world.init_resource::<LevelTimer>();
+        world.init_resource::<BestTimes>();
+        world.insert_resource(CurrentLevel::One);
+
+        assert!(finish_run(&mut world, 10.0));
+        assert_eq!(world.resource::<BestTimes>().seconds.get(&CurrentLevel::One), Some(&10.0));
+
+        // A slower second run is not a best, and leaves the first one in place.
+
+        assert!(!finish_run(&mut world, 12.0));
+        assert_eq!(world.resource::<BestTimes>().seconds.get(&CurrentLevel::One), Some(&10.0));
+
+        assert!(finish_run(&mut world, 8.0));
+        assert_eq!(world.resource::<BestTimes>().seconds.get(&CurrentLevel::One), Some(&8.0));
+    }
+}
@@ -1,10 +1,9 @@
 use once_cell::sync::Lazy;
 use uom::si::{
-    f64::{Force as UomForce, Length as UomLength, Mass as UomMass, Time as UomTime, Velocity as UomVelocity},
+    f64::{Force as UomForce, Length as UomLength, Mass as UomMass, Velocity as UomVelocity},
     force::newton,
     length::{kilometer, meter},
     mass::kilogram,
-    time::day,
     velocity::kilometer_per_second,
 };
 
@@ -13,19 +12,34 @@ use crate::shared::{SCREEN_HEIGHT_PX, SCREEN_WIDTH_PX};
 pub const PLANET_SPRITE_WIDTH_PX: f64 = 1280.0f64;
 pub const ROCKET_SPRITE_WIDTH_PX: f64 = 234.0f64;
 
-const MASS_FACTOR: f64 = 100_000_000.0f64;
-const DAYS_PER_SECOND: f64 = 0.1f64;
+pub const MASS_FACTOR: f64 = 100_000_000.0f64;
+pub const DAYS_PER_SECOND: f64 = 0.1f64;
 const GRAVITATIONAL_CONSTANT: f64 = 6.674e-11f64;
 
 const UNIT_RADIUS_KM: f64 = 60_000_000.0f64;
-const MASS_OF_SUN_KG: f64 = MASS_FACTOR * 1.989e30f64;
-const MASS_OF_EARTH_KG: f64 = MASS_FACTOR * 5.972e24f64;
+const MASS_OF_SUN_KG: f64 = 1.989e30f64;
+const MASS_OF_EARTH_KG: f64 = 5.972e24f64;
 const SCREEN_WIDTH_KM: f64 = 6_000_000_000.0f64;
 const SCREEN_HEIGHT_KM: f64 = SCREEN_WIDTH_KM * SCREEN_HEIGHT_PX / SCREEN_WIDTH_PX;
 const C_KMS: f64 = 299_792.0f64; // Speed of light in km/s.
-const MAX_PLAYER_VELOCITY_KMS: f64 = 0.99 * C_KMS; // 99% of c.
+const THRUST_VELOCITY_PER_FUEL_KMS: f64 = 1_000.0f64;
+
+pub const PHYSICS_TIMESTEP_SECONDS: f32 = 1.0f32 / 120.0f32;
+pub const MAX_PHYSICS_SUBSTEPS: usize = 64;
+pub const MAX_VELOCITY_FRACTION_OF_C: f64 = 0.9999f64;
+pub const MAX_LAUNCH_VELOCITY_FRACTION_OF_C: f64 = 0.99f64;
+pub const RELATIVISTIC_ADJUSTMENT_FLOOR: f64 = 0.1f64;
+pub const TRAJECTORY_PREVIEW_STEPS: usize = 200;
+pub const TRAJECTORY_PREVIEW_STEP_SECONDS: f64 = 1.0f64 / 60.0f64;
+pub const LAUNCH_OUTCOME_STEPS: usize = 2400;
+pub const COLLISION_WARNING_DAYS: f64 = 0.1f64;
+pub const ORBIT_TRACE_STEPS: usize = 5000;
+pub const DANGER_GLOW_RADII: f64 = 1.5f64;
+pub const PLAYER_STARTING_FUEL: f64 = 100.0f64;
+pub const FUEL_BURN_PER_SECOND: f64 = 20.0f64;
+pub const AIM_RADIANS_PER_SECOND: f64 = std::f64::consts::FRAC_PI_2;
+pub const AIM_POWER_PER_SECOND: f64 = 0.5f64;
 
-pub static DAYS_PER_SECOND_UOM: Lazy<UomTime> = Lazy::new(|| UomTime::new::<day>(DAYS_PER_SECOND));
 pub static UNIT_RADIUS: Lazy<UomLength> = Lazy::new(|| UomLength::new::<kilometer>(UNIT_RADIUS_KM));
 pub static MASS_OF_SUN: Lazy<UomMass> = Lazy::new(|| UomMass::new::<kilogram>(MASS_OF_SUN_KG));
 pub static MASS_OF_EARTH: Lazy<UomMass> = Lazy::new(|| UomMass::new::<kilogram>(MASS_OF_EARTH_KG));
@@ -34,7 +48,7 @@ pub static SCREEN_HEIGHT_UOM: Lazy<UomLength> = Lazy::new(|| UomLength::new::<ki
 pub static C: Lazy<UomVelocity> = Lazy::new(|| UomVelocity::new::<kilometer_per_second>(C_KMS));
 
 // TODO: Fix this insanity, lol.
-#[allow(clippy::type_complexity)]
+#[allow(clippy::type_complexity, unused_parens)]
 pub static G: Lazy<
     uom::si::Quantity<
         (dyn uom::si::Dimension<
@@ -64,5 +78,5 @@ pub static G: Lazy<
         / (UomMass::new::<kilogram>(1.0) * UomMass::new::<kilogram>(1.0))
 });
 
-// TODO: Make this go away, and use some acceleration.
-pub static MAX_PLAYER_LAUNCH_VELOCITY: Lazy<UomVelocity> = Lazy::new(|| UomVelocity::new::<kilometer_per_second>(MAX_PLAYER_VELOCITY_KMS));
+pub static THRUST_VELOCITY_PER_FUEL: Lazy<UomVelocity> =
+    Lazy::new(|| UomVelocity::new::<kilometer_per_second>(THRUST_VELOCITY_PER_FUEL_KMS));
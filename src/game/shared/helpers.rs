@@ -1,10 +1,19 @@
 use super::{
-    constants::{PLANET_SPRITE_WIDTH_PX, ROCKET_SPRITE_WIDTH_PX, SCREEN_HEIGHT_UOM, SCREEN_WIDTH_UOM},
-    types::{Position, Radius},
+    constants::{
+        C, DANGER_GLOW_RADII, G, MAX_VELOCITY_FRACTION_OF_C, PLANET_SPRITE_WIDTH_PX, RELATIVISTIC_ADJUSTMENT_FLOOR, ROCKET_SPRITE_WIDTH_PX,
+        SCREEN_HEIGHT_UOM, SCREEN_WIDTH_UOM,
+    },
+    types::{GravFrame, InfluenceRadius, LaunchOutcome, Mass, Position, PowerCurve, Radius, UnitSystem, Velocity},
 };
 use crate::shared::{SCREEN_HEIGHT_PX, SCREEN_WIDTH_PX};
 use bevy::prelude::*;
-use uom::si::f64::Length as UomLength;
+use glam::DVec2;
+use uom::si::{
+    acceleration::meter_per_second_squared,
+    f64::{Acceleration as UomAcceleration, Length as UomLength, Mass as UomMass, Time as UomTime, Velocity as UomVelocity},
+    time::{day, year},
+    velocity::{kilometer_per_second, meter_per_second},
+};
 
 pub fn has_collided(a: (&Position, &Radius), b: (&Position, &Radius)) -> bool {
     let a_pos = a.0;
@@ -55,3 +64,1236 @@ pub fn planet_sprite_pixel_radius_to_scale(pixels: f64) -> Vec3 {
 pub fn rocket_sprite_pixel_radius_to_scale(pixels: f64) -> Vec3 {
     Vec3::splat((2.0 * pixels / ROCKET_SPRITE_WIDTH_PX) as f32)
 }
+
+pub fn reflect_velocity(velocity: &Velocity, normal: DVec2, restitution: f64) -> Velocity {
+    // Only the component into the surface is reflected (and damped); moving away is left alone.
+
+    let normal = normal.normalize_or_zero();
+    let into_surface = velocity.x.value * normal.x + velocity.y.value * normal.y;
+
+    if into_surface >= 0.0 {
+        return *velocity;
+    }
+
+    let impulse = (1.0 + restitution) * into_surface;
+
+    Velocity {
+        x: velocity.x - UomVelocity::new::<meter_per_second>(impulse * normal.x),
+        y: velocity.y - UomVelocity::new::<meter_per_second>(impulse * normal.y),
+    }
+}
+
+pub fn gravitational_acceleration(
+    entity: Entity,
+    position: &Position,
+    masses: &[(Entity, Position, Mass, Option<InfluenceRadius>)],
+    softening: UomLength,
+) -> (UomAcceleration, UomAcceleration) {
+    let mut total_gravitational_acceleration_x = UomAcceleration::new::<meter_per_second_squared>(0.0);
+    let mut total_gravitational_acceleration_y = UomAcceleration::new::<meter_per_second_squared>(0.0);
+
+    for (other_entity, other_position, other_mass, other_influence_radius) in masses.iter() {
+        if entity == *other_entity {
+            continue;
+        }
+
+        let direction = DVec2::new((other_position.x - position.x).value, (other_position.y - position.y).value);
+        let direction = direction.normalize_or_zero();
+
+        let delta_x = position.x - other_position.x;
+        let delta_y = position.y - other_position.y;
+        let distance = (delta_x * delta_x + delta_y * delta_y).sqrt();
+
+        if other_influence_radius.is_some_and(|influence_radius| distance > influence_radius.value) {
+            continue;
+        }
+
+        // Soften very close approaches, so a body atop a mass doesn't blow up.
+
+        let distance = distance.max(softening);
+        let distance_squared = distance * distance;
+
+        let gravitational_acceleration = (*G * other_mass.value) / distance_squared;
+
+        // Floor the adjustment rather than zeroing it, so gravity stays strong right up to the horizon, where
+        // `collision_check` captures the body.
+
+        let relativistic_adjustment = (1.0 - (2.0 * *G * other_mass.value / (*C * *C * distance)).value).max(RELATIVISTIC_ADJUSTMENT_FLOOR);
+
+        total_gravitational_acceleration_x += direction.x * gravitational_acceleration * relativistic_adjustment;
+        total_gravitational_acceleration_y += direction.y * gravitational_acceleration * relativistic_adjustment;
+    }
+
+    (total_gravitational_acceleration_x, total_gravitational_acceleration_y)
+}
+
+pub fn verlet_step(
+    entity: Entity,
+    position: &mut Position,
+    velocity: &mut Velocity,
+    masses: &[(Entity, Position, Mass, Option<InfluenceRadius>)],
+    time_elapsed: UomTime,
+    softening: UomLength,
+) {
+    // Kick the velocity a half step, drift the position a full step, then finish the kick at the new position.
+
+    let (acceleration_x, acceleration_y) = gravitational_acceleration(entity, position, masses, softening);
+
+    velocity.x += acceleration_x * time_elapsed / 2.0;
+    velocity.y += acceleration_y * time_elapsed / 2.0;
+
+    position.x += velocity.x * time_elapsed;
+    position.y += velocity.y * time_elapsed;
+
+    let (acceleration_x, acceleration_y) = gravitational_acceleration(entity, position, masses, softening);
+
+    velocity.x += acceleration_x * time_elapsed / 2.0;
+    velocity.y += acceleration_y * time_elapsed / 2.0;
+
+    // Keep the velocity sub-luminal, preserving direction.
+
+    velocity.clamp_to_speed(MAX_VELOCITY_FRACTION_OF_C * *C);
+}
+
+pub fn predict_trajectory(
+    entity: Entity,
+    mut position: Position,
+    mut velocity: Velocity,
+    masses: &[(Entity, Position, Mass, Option<InfluenceRadius>)],
+    time_step: UomTime,
+    softening: UomLength,
+    steps: usize,
+) -> Vec<Position> {
+    let mut trajectory = Vec::with_capacity(steps + 1);
+
+    trajectory.push(position);
+
+    for _ in 0..steps {
+        verlet_step(entity, &mut position, &mut velocity, masses, time_step, softening);
+        trajectory.push(position);
+    }
+
+    trajectory
+}
+
+/// Like `predict_trajectory`, but stops early once the path closes back on its start or leaves the space around
+/// the screen.
+pub fn trace_orbit(
+    entity: Entity,
+    mut position: Position,
+    mut velocity: Velocity,
+    masses: &[(Entity, Position, Mass, Option<InfluenceRadius>)],
+    time_step: UomTime,
+    softening: UomLength,
+    max_steps: usize,
+) -> Vec<Position> {
+    let start = position;
+    let closing_distance = *SCREEN_WIDTH_UOM / 100.0;
+    let mut has_left_start = false;
+
+    let mut trace = vec![start];
+
+    for _ in 0..max_steps {
+        verlet_step(entity, &mut position, &mut velocity, masses, time_step, softening);
+        trace.push(position);
+
+        let delta_x = position.x - start.x;
+        let delta_y = position.y - start.y;
+        let distance_from_start = (delta_x * delta_x + delta_y * delta_y).sqrt();
+
+        if distance_from_start > 2.0 * closing_distance {
+            has_left_start = true;
+        } else if has_left_start && distance_from_start < closing_distance {
+            break;
+        }
+
+        let is_out_of_bounds = position.x < -*SCREEN_WIDTH_UOM
+            || position.x > 2.0 * *SCREEN_WIDTH_UOM
+            || position.y < -*SCREEN_HEIGHT_UOM
+            || position.y > 2.0 * *SCREEN_HEIGHT_UOM;
+
+        if is_out_of_bounds {
+            break;
+        }
+    }
+
+    trace
+}
+
+/// The first step along `path` at which a body of `radius` touches any of `bodies`.
+pub fn frames_to_collision(path: &[Position], bodies: &[(Position, Radius)], radius: &Radius) -> Option<usize> {
+    path.iter().position(|position| {
+        bodies
+            .iter()
+            .any(|(body_position, body_radius)| has_collided((position, radius), (body_position, body_radius)))
+    })
+}
+
+/// Runs a launch forward until it reaches the destination, crashes into a body or a black hole's horizon, or leaves
+/// the space around the screen; anything still flying after `max_steps` counts as orbiting. Returns the outcome along
+/// with the path flown, one position per step.
+#[allow(clippy::too_many_arguments)]
+pub fn simulate_launch_outcome(
+    entity: Entity,
+    mut position: Position,
+    mut velocity: Velocity,
+    radius: &Radius,
+    masses: &[(Entity, Position, Mass, Option<InfluenceRadius>)],
+    bodies: &[(Entity, Position, Radius)],
+    black_holes: &[(Entity, Position, Mass)],
+    destination: (&Position, &Radius),
+    time_step: UomTime,
+    softening: UomLength,
+    max_steps: usize,
+) -> (LaunchOutcome, Vec<Position>) {
+    let mut path = Vec::with_capacity(max_steps + 1);
+
+    for _ in 0..=max_steps {
+        path.push(position);
+
+        if has_collided((&position, radius), destination) {
+            return (LaunchOutcome::Hit, path);
+        }
+
+        if let Some((body_entity, ..)) = bodies
+            .iter()
+            .find(|(_, body_position, body_radius)| has_collided((&position, radius), (body_position, body_radius)))
+        {
+            return (LaunchOutcome::CrashInto(*body_entity), path);
+        }
+
+        let horizon = black_holes.iter().find(|(_, black_hole_position, black_hole_mass)| {
+            let delta_x = position.x - black_hole_position.x;
+            let delta_y = position.y - black_hole_position.y;
+
+            (delta_x * delta_x + delta_y * delta_y).sqrt() <= schwarzschild_radius(black_hole_mass.value)
+        });
+
+        if let Some((black_hole_entity, ..)) = horizon {
+            return (LaunchOutcome::CrashInto(*black_hole_entity), path);
+        }
+
+        let is_out_of_bounds = position.x < -*SCREEN_WIDTH_UOM
+            || position.x > 2.0 * *SCREEN_WIDTH_UOM
+            || position.y < -*SCREEN_HEIGHT_UOM
+            || position.y > 2.0 * *SCREEN_HEIGHT_UOM;
+
+        if is_out_of_bounds {
+            return (LaunchOutcome::Escaped, path);
+        }
+
+        verlet_step(entity, &mut position, &mut velocity, masses, time_step, softening);
+    }
+
+    (LaunchOutcome::Orbiting, path)
+}
+
+/// Simulated (observer) time until a launch reaches the destination, per `simulate_launch_outcome`, or `None` if it
+/// crashes, escapes, or is still flying after `max_steps`.
+#[allow(clippy::too_many_arguments)]
+pub fn predict_destination_arrival(
+    entity: Entity,
+    position: Position,
+    velocity: Velocity,
+    radius: &Radius,
+    masses: &[(Entity, Position, Mass, Option<InfluenceRadius>)],
+    bodies: &[(Entity, Position, Radius)],
+    black_holes: &[(Entity, Position, Mass)],
+    destination: (&Position, &Radius),
+    time_step: UomTime,
+    softening: UomLength,
+    max_steps: usize,
+) -> Option<UomTime> {
+    let (outcome, path) = simulate_launch_outcome(
+        entity,
+        position,
+        velocity,
+        radius,
+        masses,
+        bodies,
+        black_holes,
+        destination,
+        time_step,
+        softening,
+        max_steps,
+    );
+
+    (outcome == LaunchOutcome::Hit).then(|| time_step * path.len().saturating_sub(1) as f64)
+}
+
+pub fn doppler_color(base: Color, beta: f64, cos_theta: f64) -> Color {
+    let gamma = 1.0 / (1.0 - beta * beta).sqrt();
+    let doppler_factor = 1.0 / (gamma * (1.0 - beta * cos_theta));
+
+    // Positive shifts are toward the observer (blue), negative are away (red).
+
+    let shift = doppler_factor.ln().clamp(-1.0, 1.0) as f32;
+    let target = if shift >= 0.0 {
+        Color::rgb(0.3, 0.5, 1.0)
+    } else {
+        Color::rgb(1.0, 0.3, 0.2)
+    };
+    let amount = shift.abs();
+
+    Color::rgba(
+        base.r() + (target.r() - base.r()) * amount,
+        base.g() + (target.g() - base.g()) * amount,
+        base.b() + (target.b() - base.b()) * amount,
+        base.a(),
+    )
+}
+
+/// How close a body is to a surface, from 0 at `DANGER_GLOW_RADII` radii from the center to 1 at the surface.
+pub fn danger_intensity(distance: UomLength, radius: UomLength) -> f32 {
+    let radii = (distance / radius).value;
+
+    ((DANGER_GLOW_RADII - radii) / (DANGER_GLOW_RADII - 1.0)).clamp(0.0, 1.0) as f32
+}
+
+pub fn schwarzschild_radius(mass: UomMass) -> UomLength {
+    2.0 * *G * mass / (*C * *C)
+}
+
+pub fn circular_orbit_velocity(central_mass: UomMass, radius: UomLength) -> UomVelocity {
+    // Match `gravitational_acceleration`, which weakens the Newtonian pull by the same floored adjustment.
+
+    let relativistic_adjustment = (1.0 - (schwarzschild_radius(central_mass) / radius).value).max(RELATIVISTIC_ADJUSTMENT_FLOOR);
+
+    (*G * central_mass * relativistic_adjustment / radius).sqrt()
+}
+
+pub fn circular_orbit_velocity_vector(center: &Position, central_mass: UomMass, position: &Position) -> Velocity {
+    let delta_x = position.x - center.x;
+    let delta_y = position.y - center.y;
+    let radius = (delta_x * delta_x + delta_y * delta_y).sqrt();
+
+    let speed = circular_orbit_velocity(central_mass, radius);
+
+    // Counter-clockwise, perpendicular to the radius vector.
+
+    Velocity {
+        x: -speed * (delta_y / radius).value,
+        y: speed * (delta_x / radius).value,
+    }
+}
+
+/// The circular-orbit velocity at the current radius, tangential in whichever sense the body already circles; its speed
+/// comes from `circular_orbit_velocity`, so the orbit holds under the game's force law rather than Newton's.
+pub fn nudge_to_circular_orbit(velocity: &Velocity, position: &Position, central: (&Position, UomMass)) -> Velocity {
+    let (center, central_mass) = central;
+    let circular = circular_orbit_velocity_vector(center, central_mass, position);
+
+    // `circular_orbit_velocity_vector` runs counter-clockwise, so flip it for a clockwise orbit.
+
+    let radial = DVec2::new((position.x - center.x).value, (position.y - center.y).value);
+    let is_clockwise = radial.perp_dot(DVec2::new(velocity.x.value, velocity.y.value)) < 0.0;
+
+    if is_clockwise {
+        Velocity { x: -circular.x, y: -circular.y }
+    } else {
+        circular
+    }
+}
+
+pub fn is_bound_orbit(
+    player_velocity: UomVelocity,
+    player_position: &Position,
+    masses: &[(Entity, Position, Mass, Option<InfluenceRadius>)],
+) -> bool {
+    // Compare specific kinetic energy against the total specific gravitational potential energy.
+
+    let kinetic_energy = 0.5 * (player_velocity * player_velocity).value;
+
+    let potential_energy = -gravitational_potential(player_position, masses);
+
+    kinetic_energy < potential_energy
+}
+
+pub fn gravitational_gamma(
+    entity: Entity,
+    position: &Position,
+    masses: &[(Entity, Position, Mass, Option<InfluenceRadius>)],
+    softening: UomLength,
+) -> f64 {
+    gravitational_gamma_from_terms(&gravitational_gamma_terms(entity, position, masses, softening))
+}
+
+/// The gravitational gamma for a set of `gravitational_gamma_terms`; none at all gives 1.
+pub fn gravitational_gamma_from_terms(terms: &[(Entity, f64)]) -> f64 {
+    // Sum the potentials under a single root, rather than multiplying per-mass factors.
+
+    let total: f64 = terms.iter().map(|(_, term)| term).sum();
+
+    1.0 / (1.0 - total.min(0.9999)).sqrt()
+}
+
+/// Each mass's `2GM/(c²r)` term, which `gravitational_gamma` sums under a single root; like the force, a mass is
+/// ignored beyond its `InfluenceRadius`.
+pub fn gravitational_gamma_terms(
+    entity: Entity,
+    position: &Position,
+    masses: &[(Entity, Position, Mass, Option<InfluenceRadius>)],
+    softening: UomLength,
+) -> Vec<(Entity, f64)> {
+    masses
+        .iter()
+        .filter(|(other_entity, ..)| *other_entity != entity)
+        .filter_map(|(other_entity, other_position, other_mass, other_influence_radius)| {
+            let delta_x = position.x - other_position.x;
+            let delta_y = position.y - other_position.y;
+            let distance = (delta_x * delta_x + delta_y * delta_y).sqrt();
+
+            if other_influence_radius.is_some_and(|influence_radius| distance > influence_radius.value) {
+                return None;
+            }
+
+            let distance = distance.max(softening);
+
+            Some((*other_entity, (2.0 * *G * other_mass.value / (*C * *C * distance)).value))
+        })
+        .collect()
+}
+
+/// The terms behind `gravitational_gamma_in_frame`: the player's, and the observer's at `reference` when `frame` is
+/// local (none at infinity).
+#[allow(clippy::type_complexity)]
+pub fn gravitational_gamma_breakdown(
+    frame: GravFrame,
+    entity: Entity,
+    position: &Position,
+    reference: &Position,
+    masses: &[(Entity, Position, Mass, Option<InfluenceRadius>)],
+    softening: UomLength,
+) -> (Vec<(Entity, f64)>, Vec<(Entity, f64)>) {
+    let player_terms = gravitational_gamma_terms(entity, position, masses, softening);
+    let observer_terms = match frame {
+        GravFrame::AtInfinity => Vec::new(),
+        GravFrame::Local => gravitational_gamma_terms(entity, reference, masses, softening),
+    };
+
+    (player_terms, observer_terms)
+}
+
+/// The player's gravitational gamma as seen by `frame`'s observer; the local observer hovers at `reference`.
+pub fn gravitational_gamma_in_frame(
+    frame: GravFrame,
+    entity: Entity,
+    position: &Position,
+    reference: &Position,
+    masses: &[(Entity, Position, Mass, Option<InfluenceRadius>)],
+    softening: UomLength,
+) -> f64 {
+    let (player_terms, observer_terms) = gravitational_gamma_breakdown(frame, entity, position, reference, masses, softening);
+
+    // Both clocks are slowed relative to infinity, so the local observer sees the ratio: below 1 when the player is
+    // higher in the potential than the observer. At infinity, there are no observer terms, and the ratio is to 1.
+
+    gravitational_gamma_from_terms(&player_terms) / gravitational_gamma_from_terms(&observer_terms)
+}
+
+pub fn sample_gravitational_gamma_grid(
+    columns: usize,
+    rows: usize,
+    masses: &[(Entity, Position, Mass, Option<InfluenceRadius>)],
+    softening: UomLength,
+) -> Vec<f64> {
+    // Row-major from the bottom-left, sampling each cell at its center.
+
+    (0..rows)
+        .flat_map(|row| (0..columns).map(move |column| (column, row)))
+        .map(|(column, row)| {
+            let position = get_position_from_percentage((column as f64 + 0.5) / columns as f64, (row as f64 + 0.5) / rows as f64);
+
+            gravitational_gamma(Entity::PLACEHOLDER, &position, masses, softening)
+        })
+        .collect()
+}
+
+pub fn sample_gravitational_field_grid(
+    columns: usize,
+    rows: usize,
+    masses: &[(Entity, Position, Mass, Option<InfluenceRadius>)],
+    softening: UomLength,
+) -> Vec<(Position, DVec2)> {
+    // Row-major from the bottom-left, sampling each cell at its center, with the field in m/s^2.
+
+    (0..rows)
+        .flat_map(|row| (0..columns).map(move |column| (column, row)))
+        .map(|(column, row)| {
+            let position = get_position_from_percentage((column as f64 + 0.5) / columns as f64, (row as f64 + 0.5) / rows as f64);
+            let (acceleration_x, acceleration_y) = gravitational_acceleration(Entity::PLACEHOLDER, &position, masses, softening);
+
+            (position, DVec2::new(acceleration_x.value, acceleration_y.value))
+        })
+        .collect()
+}
+
+pub fn gravitational_potential(position: &Position, masses: &[(Entity, Position, Mass, Option<InfluenceRadius>)]) -> f64 {
+    // Sum of the specific potentials (-GM/r) of every mass within its influence radius, in J/kg.
+
+    masses
+        .iter()
+        .map(|(_, other_position, other_mass, other_influence_radius)| {
+            let delta_x = position.x - other_position.x;
+            let delta_y = position.y - other_position.y;
+            let distance = (delta_x * delta_x + delta_y * delta_y).sqrt();
+
+            if distance.value == 0.0 || other_influence_radius.is_some_and(|influence_radius| distance > influence_radius.value) {
+                return 0.0;
+            }
+
+            -(*G * other_mass.value / distance).value
+        })
+        .sum()
+}
+
+pub fn relativistic_kinetic_energy(speed: UomVelocity) -> f64 {
+    // Specific kinetic energy, (γ - 1)c², in J/kg.
+
+    let beta = (speed / *C).value;
+    let gamma = 1.0 / (1.0 - beta * beta).sqrt();
+
+    (gamma - 1.0) * (*C * *C).value
+}
+
+pub fn total_energy(velocity: &Velocity, position: &Position, masses: &[(Entity, Position, Mass, Option<InfluenceRadius>)]) -> f64 {
+    relativistic_kinetic_energy(velocity.scalar()) + gravitational_potential(position, masses)
+}
+
+pub fn sample_potential_cross_section(
+    y: UomLength,
+    x_start: UomLength,
+    x_end: UomLength,
+    masses: &[(Entity, Position, Mass, Option<InfluenceRadius>)],
+    samples: usize,
+) -> Vec<(UomLength, f64)> {
+    if samples < 2 {
+        return Vec::new();
+    }
+
+    (0..samples)
+        .map(|k| {
+            let x = x_start + (x_end - x_start) * (k as f64 / (samples - 1) as f64);
+
+            (x, gravitational_potential(&Position { x, y }, masses))
+        })
+        .collect()
+}
+
+pub fn format_time(time: UomTime, unit_system: UnitSystem) -> String {
+    match unit_system {
+        UnitSystem::SI if time.abs().get::<year>() >= 1.0 => format_years_and_days(time),
+        UnitSystem::SI => format!("{:2.2}d", time.get::<day>()),
+        UnitSystem::Natural => format!("{:.4} yr", time.get::<year>()),
+    }
+}
+
+/// Splits long spans into whole years and the days left over (e.g., "3y 120d"), so they stay readable.
+pub fn format_years_and_days(time: UomTime) -> String {
+    let sign = if time.value < 0.0 { "-" } else { "" };
+    let time = time.abs();
+
+    let years = time.get::<year>().floor();
+    let days = (time - UomTime::new::<year>(years)).get::<day>().floor();
+
+    format!("{}{}y {}d", sign, years, days)
+}
+
+pub fn format_velocity(velocity: UomVelocity, unit_system: UnitSystem) -> String {
+    match unit_system {
+        UnitSystem::SI => format!("{:.0} km/s", velocity.get::<kilometer_per_second>()),
+        UnitSystem::Natural => format!("{:.4}c", (velocity / *C).value),
+    }
+}
+
+pub fn format_time_difference(observer_time: UomTime, player_time: UomTime, unit_system: UnitSystem) -> String {
+    format!("Δt = {}", format_time(observer_time - player_time, unit_system))
+}
+
+/// Moves `current` exponentially toward `target`; frame-rate independent, and never overshoots.
+pub fn ease_toward(current: f64, target: f64, rate: f64, dt: f64) -> f64 {
+    target + (current - target) * (-rate * dt).exp()
+}
+
+pub fn map_power(curve: PowerCurve, t: f64) -> f64 {
+    let t = t.clamp(0.0, 1.0);
+
+    match curve {
+        PowerCurve::Linear => t,
+        PowerCurve::Quadratic => t * t,
+        PowerCurve::Exponential { k } if k.abs() < f32::EPSILON => t,
+        PowerCurve::Exponential { k } => (k as f64 * t).exp_m1() / (k as f64).exp_m1(),
+    }
+}
+
+/// The inverse of `map_power`: the raw aim needed to get a given effective power.
+pub fn unmap_power(curve: PowerCurve, power: f64) -> f64 {
+    let power = power.clamp(0.0, 1.0);
+
+    match curve {
+        PowerCurve::Linear => power,
+        PowerCurve::Quadratic => power.sqrt(),
+        PowerCurve::Exponential { k } if k.abs() < f32::EPSILON => power,
+        PowerCurve::Exponential { k } => (power * (k as f64).exp_m1()).ln_1p() / k as f64,
+    }
+}
+
+pub fn nearest_mass(position: &Position, masses: &[(Entity, Position, Mass, Option<InfluenceRadius>)]) -> Option<(Position, Mass)> {
+    masses
+        .iter()
+        .map(|(_, other_position, other_mass, _)| {
+            let delta_x = position.x - other_position.x;
+            let delta_y = position.y - other_position.y;
+
+            ((delta_x * delta_x + delta_y * delta_y).value, *other_position, *other_mass)
+        })
+        .min_by(|(a, ..), (b, ..)| a.total_cmp(b))
+        .map(|(_, other_position, other_mass)| (other_position, other_mass))
+}
+
+pub fn contraction_scale(beta: f64, direction: Vec2) -> Vec2 {
+    // Contract by `1/γ` along the direction of motion, leaving the perpendicular axis alone.
+
+    let contraction = (1.0 - beta * beta).sqrt() as f32;
+    let direction = direction.normalize_or_zero().abs();
+
+    Vec2::ONE - (1.0 - contraction) * direction
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game::shared::constants::{MASS_OF_EARTH, MASS_OF_SUN};
+    use uom::si::{length::meter, time::second};
+
+    fn sun_at_origin() -> Vec<(Entity, Position, Mass, Option<InfluenceRadius>)> {
+        vec![(Entity::from_raw(1), Position::default(), Mass { value: *MASS_OF_SUN }, None)]
+    }
+
+    fn position_at(x: UomLength) -> Position {
+        Position { x, ..Default::default() }
+    }
+
+    fn assert_close(actual: f64, expected: f64) {
+        assert!((actual - expected).abs() < 1e-9, "{} is not close to {}", actual, expected);
+    }
+
+    #[test]
+    fn gravitational_gamma_in_frame_at_infinity() {
+        let masses = sun_at_origin();
+        let r_s = schwarzschild_radius(*MASS_OF_SUN);
+        let gamma = gravitational_gamma_in_frame(
+            GravFrame::AtInfinity,
+            Entity::from_raw(0),
+            &position_at(4.0 * r_s),
+            &position_at(2.0 * r_s),
+            &masses,
+            UomLength::new::<meter>(1.0),
+        );
+
+        assert_close(gamma, 1.0 / 0.75f64.sqrt());
+    }
+
+    #[test]
+    fn gravitational_gamma_in_frame_local() {
+        let masses = sun_at_origin();
+        let r_s = schwarzschild_radius(*MASS_OF_SUN);
+        let gamma = gravitational_gamma_in_frame(
+            GravFrame::Local,
+            Entity::from_raw(0),
+            &position_at(4.0 * r_s),
+            &position_at(2.0 * r_s),
+            &masses,
+            UomLength::new::<meter>(1.0),
+        );
+
+        // The player is higher up than the observer, so ticks faster from the observer's point of view.
+
+        assert_close(gamma, (0.5f64 / 0.75).sqrt());
+    }
+
+    #[test]
+    fn gravitational_gamma_in_frame_local_at_the_observer() {
+        let masses = sun_at_origin();
+        let r_s = schwarzschild_radius(*MASS_OF_SUN);
+        let gamma = gravitational_gamma_in_frame(
+            GravFrame::Local,
+            Entity::from_raw(0),
+            &position_at(3.0 * r_s),
+            &position_at(3.0 * r_s),
+            &masses,
+            UomLength::new::<meter>(1.0),
+        );
+
+        assert_close(gamma, 1.0);
+    }
+
+    #[test]
+    fn gravitational_gamma_ignores_masses_beyond_their_influence() {
+        let r_s = schwarzschild_radius(*MASS_OF_SUN);
+        let masses = vec![(
+            Entity::from_raw(1),
+            Position::default(),
+            Mass { value: *MASS_OF_SUN },
+            Some(InfluenceRadius { value: 3.0 * r_s }),
+        )];
+
+        let inside = gravitational_gamma(Entity::from_raw(0), &position_at(2.0 * r_s), &masses, UomLength::new::<meter>(1.0));
+        let outside = gravitational_gamma(Entity::from_raw(0), &position_at(4.0 * r_s), &masses, UomLength::new::<meter>(1.0));
+
+        assert_close(inside, 1.0 / 0.5f64.sqrt());
+        assert_close(outside, 1.0);
+        assert!(gravitational_potential(&position_at(2.0 * r_s), &masses) < 0.0);
+        assert_close(gravitational_potential(&position_at(4.0 * r_s), &masses), 0.0);
+    }
+
+    #[test]
+    fn format_time_below_a_year_is_in_days() {
+        assert_eq!(format_time(UomTime::new::<day>(364.0), UnitSystem::SI), "364.00d");
+        assert_eq!(format_time(UomTime::new::<day>(-2.5), UnitSystem::SI), "-2.50d");
+    }
+
+    #[test]
+    fn format_time_from_a_year_is_in_years_and_days() {
+        assert_eq!(format_time(UomTime::new::<year>(1.0), UnitSystem::SI), "1y 0d");
+        assert_eq!(format_time(UomTime::new::<day>(485.0), UnitSystem::SI), "1y 120d");
+        assert_eq!(format_time(UomTime::new::<day>(-485.0), UnitSystem::SI), "-1y 120d");
+    }
+
+    #[test]
+    fn format_time_in_natural_units_is_in_years() {
+        assert_eq!(format_time(UomTime::new::<year>(0.5), UnitSystem::Natural), "0.5000 yr");
+    }
+
+    #[test]
+    fn gravitational_gamma_for_a_single_mass() {
+        let masses = sun_at_origin();
+        let r_s = schwarzschild_radius(*MASS_OF_SUN);
+        let gamma = gravitational_gamma(Entity::from_raw(0), &position_at(5.0 * r_s), &masses, UomLength::new::<meter>(1.0));
+
+        assert_close(gamma, 1.0 / 0.8f64.sqrt());
+    }
+
+    #[test]
+    fn gravitational_gamma_sums_potentials_under_one_root() {
+        let r_s = schwarzschild_radius(*MASS_OF_SUN);
+        let masses = vec![
+            (Entity::from_raw(1), Position::default(), Mass { value: *MASS_OF_SUN }, None),
+            (Entity::from_raw(2), position_at(8.0 * r_s), Mass { value: *MASS_OF_SUN }, None),
+        ];
+        let gamma = gravitational_gamma(Entity::from_raw(0), &position_at(4.0 * r_s), &masses, UomLength::new::<meter>(1.0));
+
+        // Each mass is 4 r_s away, so contributes 1/4; the product of per-mass factors would give 1/0.75 instead.
+
+        assert_close(gamma, 1.0 / 0.5f64.sqrt());
+    }
+
+    #[test]
+    fn frames_to_collision_finds_the_first_touching_step() {
+        let unit = UomLength::new::<meter>(1.0);
+        let path: Vec<_> = (0..10).map(|step| position_at(step as f64 * unit)).collect();
+        let bodies = [(position_at(7.0 * unit), Radius { value: unit })];
+
+        assert_eq!(frames_to_collision(&path, &bodies, &Radius { value: 0.5 * unit }), Some(6));
+    }
+
+    #[test]
+    fn frames_to_collision_is_none_for_a_clear_path() {
+        let unit = UomLength::new::<meter>(1.0);
+        let path: Vec<_> = (0..10).map(|step| position_at(step as f64 * unit)).collect();
+        let bodies = [(position_at(20.0 * unit), Radius { value: unit })];
+
+        assert_eq!(frames_to_collision(&path, &bodies, &Radius { value: 0.5 * unit }), None);
+    }
+
+    #[test]
+    fn schwarzschild_radius_of_the_sun() {
+        // The reference value is about 2953 m; allow 0.1% for the rounded constants.
+
+        let r_s = schwarzschild_radius(*MASS_OF_SUN).get::<meter>();
+
+        assert!((r_s - 2953.0).abs() < 3.0, "{} m", r_s);
+    }
+
+    #[test]
+    fn schwarzschild_radius_scales_with_mass() {
+        let r_s = schwarzschild_radius(*MASS_OF_SUN);
+
+        assert_close((schwarzschild_radius(10.0 * *MASS_OF_SUN) / r_s).value, 10.0);
+    }
+
+    #[test]
+    fn danger_intensity_is_zero_when_far() {
+        let radius = UomLength::new::<meter>(10.0);
+
+        assert_eq!(danger_intensity(DANGER_GLOW_RADII * radius, radius), 0.0);
+        assert_eq!(danger_intensity(10.0 * radius, radius), 0.0);
+    }
+
+    #[test]
+    fn danger_intensity_rises_to_one_at_the_surface() {
+        let radius = UomLength::new::<meter>(10.0);
+        let halfway = danger_intensity((1.0 + DANGER_GLOW_RADII) / 2.0 * radius, radius);
+
+        assert!((halfway - 0.5).abs() < 1e-6);
+        assert_eq!(danger_intensity(radius, radius), 1.0);
+        assert_eq!(danger_intensity(0.5 * radius, radius), 1.0);
+    }
+
+    #[test]
+    fn format_velocity_in_each_unit_system() {
+        let velocity = 0.5 * *C;
+
+        assert_eq!(format_velocity(velocity, UnitSystem::SI), "149896 km/s");
+        assert_eq!(format_velocity(velocity, UnitSystem::Natural), "0.5000c");
+    }
+
+    #[test]
+    fn format_time_difference_in_each_unit_system() {
+        let observer_time = UomTime::new::<day>(3.0);
+        let player_time = UomTime::new::<day>(1.0);
+
+        assert_eq!(format_time_difference(observer_time, player_time, UnitSystem::SI), "Δt = 2.00d");
+        assert_eq!(
+            format_time_difference(UomTime::new::<year>(1.0), UomTime::new::<year>(0.0), UnitSystem::Natural),
+            "Δt = 1.0000 yr"
+        );
+    }
+
+    #[test]
+    fn ease_toward_converges_to_the_target() {
+        let mut current = 0.0;
+
+        for _ in 0..1000 {
+            current = ease_toward(current, 10.0, 10.0, 1.0 / 60.0);
+        }
+
+        assert_close(current, 10.0);
+    }
+
+    #[test]
+    fn ease_toward_never_overshoots() {
+        for (current, target) in [(0.0, 10.0), (10.0, 0.0)] {
+            let next = ease_toward(current, target, 1000.0, 1.0);
+
+            assert!((next - target).abs() <= (current - target).abs());
+            assert!((next - target) * (current - target) >= 0.0);
+        }
+    }
+
+    #[test]
+    fn ease_toward_is_a_no_op_at_the_target() {
+        assert_eq!(ease_toward(5.0, 5.0, 10.0, 1.0 / 60.0), 5.0);
+    }
+
+    #[test]
+    fn circular_orbit_velocity_balances_the_force_law() {
+        let masses = sun_at_origin();
+        let r_s = schwarzschild_radius(*MASS_OF_SUN);
+
+        // Close in, the relativistic adjustment matters; the speed must still give exactly the centripetal pull.
+
+        for radius in [2.0 * r_s, 5.0 * r_s, 100.0 * r_s] {
+            let speed = circular_orbit_velocity(*MASS_OF_SUN, radius);
+            let (acceleration_x, acceleration_y) =
+                gravitational_acceleration(Entity::from_raw(0), &position_at(radius), &masses, UomLength::new::<meter>(1.0));
+            let acceleration = (acceleration_x * acceleration_x + acceleration_y * acceleration_y).sqrt();
+
+            assert_close((speed * speed / radius / acceleration).value, 1.0);
+        }
+    }
+
+    fn launch_outcome(
+        velocity: Velocity,
+        masses: &[(Entity, Position, Mass, Option<InfluenceRadius>)],
+        bodies: &[(Entity, Position, Radius)],
+        destination: Position,
+        time_step: UomTime,
+        max_steps: usize,
+    ) -> LaunchOutcome {
+        let unit = UomLength::new::<meter>(1.0);
+
+        simulate_launch_outcome(
+            Entity::from_raw(0),
+            Position::default(),
+            velocity,
+            &Radius { value: 0.5 * unit },
+            masses,
+            bodies,
+            &[],
+            (&destination, &Radius { value: unit }),
+            time_step,
+            unit,
+            max_steps,
+        )
+        .0
+    }
+
+    fn velocity_x(speed: UomVelocity) -> Velocity {
+        Velocity { x: speed, ..Default::default() }
+    }
+
+    #[test]
+    fn simulate_launch_outcome_hits_a_destination_in_the_way() {
+        let outcome = launch_outcome(
+            velocity_x(UomVelocity::new::<meter_per_second>(1.0)),
+            &[],
+            &[],
+            position_at(UomLength::new::<meter>(10.0)),
+            UomTime::new::<second>(1.0),
+            100,
+        );
+
+        assert_eq!(outcome, LaunchOutcome::Hit);
+    }
+
+    #[test]
+    fn simulate_launch_outcome_crashes_into_a_body_in_the_way() {
+        let body = Entity::from_raw(5);
+        let outcome = launch_outcome(
+            velocity_x(UomVelocity::new::<meter_per_second>(1.0)),
+            &[],
+            &[(
+                body,
+                position_at(UomLength::new::<meter>(10.0)),
+                Radius { value: UomLength::new::<meter>(1.0) },
+            )],
+            position_at(UomLength::new::<meter>(20.0)),
+            UomTime::new::<second>(1.0),
+            100,
+        );
+
+        assert_eq!(outcome, LaunchOutcome::CrashInto(body));
+    }
+
+    #[test]
+    fn simulate_launch_outcome_escapes_off_screen() {
+        // Each step covers a screen width, leaving the space around the screen within three steps, well clear of the
+        // destination.
+
+        let speed = 0.5 * *C;
+        let outcome = launch_outcome(
+            velocity_x(speed),
+            &[],
+            &[],
+            Position {
+                y: UomLength::new::<meter>(100.0),
+                ..Default::default()
+            },
+            *SCREEN_WIDTH_UOM / speed,
+            100,
+        );
+
+        assert_eq!(outcome, LaunchOutcome::Escaped);
+    }
+
+    #[test]
+    fn simulate_launch_outcome_is_orbiting_when_still_circling() {
+        // Two full turns of a circular orbit around the sun, with the destination far off the orbit.
+
+        let masses = sun_at_origin();
+        let radius = UomLength::new::<meter>(1e9);
+        let start = position_at(radius);
+        let velocity = circular_orbit_velocity_vector(&Position::default(), *MASS_OF_SUN, &start);
+        let period = 2.0 * std::f64::consts::PI * radius / velocity.scalar();
+
+        let (outcome, path) = simulate_launch_outcome(
+            Entity::from_raw(0),
+            start,
+            velocity,
+            &Radius { value: UomLength::new::<meter>(1.0) },
+            &masses,
+            &[],
+            &[],
+            (&position_at(100.0 * radius), &Radius { value: UomLength::new::<meter>(1.0) }),
+            period / 100.0,
+            UomLength::new::<meter>(1.0),
+            200,
+        );
+
+        assert_eq!(outcome, LaunchOutcome::Orbiting);
+        assert_eq!(path.len(), 201);
+    }
+
+    #[test]
+    fn doppler_color_at_rest_is_the_base_color() {
+        let base = Color::rgba(0.5, 0.5, 0.5, 0.8);
+
+        assert_eq!(doppler_color(base, 0.0, 1.0), base);
+        assert_eq!(doppler_color(base, 0.0, -1.0), base);
+    }
+
+    #[test]
+    fn doppler_color_shifts_blue_toward_the_observer() {
+        let base = Color::rgba(0.5, 0.5, 0.5, 0.8);
+        let shifted = doppler_color(base, 0.9, 1.0);
+
+        assert!(shifted.b() > base.b());
+        assert!(shifted.r() < base.r());
+        assert_eq!(shifted.a(), base.a());
+    }
+
+    #[test]
+    fn doppler_color_shifts_red_away_from_the_observer() {
+        let base = Color::rgba(0.5, 0.5, 0.5, 0.8);
+        let shifted = doppler_color(base, 0.9, -1.0);
+
+        assert!(shifted.r() > base.r());
+        assert!(shifted.b() < base.b());
+        assert_eq!(shifted.a(), base.a());
+    }
+
+    #[test]
+    fn is_bound_orbit_for_a_slow_player_deep_in_a_well() {
+        let masses = sun_at_origin();
+        let position = position_at(UomLength::new::<meter>(1e9));
+
+        assert!(is_bound_orbit(UomVelocity::new::<meter_per_second>(1e3), &position, &masses));
+    }
+
+    #[test]
+    fn is_bound_orbit_is_false_above_escape_velocity() {
+        let masses = sun_at_origin();
+        let radius = UomLength::new::<meter>(1e9);
+        let escape_velocity = (2.0 * *G * *MASS_OF_SUN / radius).sqrt();
+
+        assert!(is_bound_orbit(0.9 * escape_velocity, &position_at(radius), &masses));
+        assert!(!is_bound_orbit(1.1 * escape_velocity, &position_at(radius), &masses));
+    }
+
+    #[test]
+    fn is_bound_orbit_is_false_with_no_masses_nearby() {
+        let position = position_at(UomLength::new::<meter>(1e9));
+        let slow = UomVelocity::new::<meter_per_second>(1.0);
+
+        assert!(!is_bound_orbit(slow, &position, &[]));
+
+        // A mass whose influence stops short of the player doesn't count either.
+
+        let out_of_reach = [(
+            Entity::from_raw(1),
+            Position::default(),
+            Mass { value: *MASS_OF_SUN },
+            Some(InfluenceRadius { value: UomLength::new::<meter>(1e8) }),
+        )];
+
+        assert!(!is_bound_orbit(slow, &position, &out_of_reach));
+    }
+
+    #[test]
+    fn contraction_scale_at_rest_is_no_squash() {
+        assert_eq!(contraction_scale(0.0, Vec2::X), Vec2::ONE);
+        assert_eq!(contraction_scale(0.0, Vec2::new(1.0, 1.0)), Vec2::ONE);
+    }
+
+    #[test]
+    fn contraction_scale_squashes_along_the_motion() {
+        // At 0.8c, γ = 5/3, so the motion axis shrinks to 0.6.
+
+        assert!(contraction_scale(0.8, Vec2::X).abs_diff_eq(Vec2::new(0.6, 1.0), 1e-6));
+        assert!(contraction_scale(0.8, Vec2::NEG_Y).abs_diff_eq(Vec2::new(1.0, 0.6), 1e-6));
+    }
+
+    #[test]
+    fn nudge_to_circular_orbit_is_circular_speed_square_to_the_radius() {
+        let center = Position {
+            x: UomLength::new::<meter>(1e9),
+            y: UomLength::new::<meter>(2e9),
+        };
+        let position = Position {
+            x: UomLength::new::<meter>(4e9),
+            y: UomLength::new::<meter>(6e9),
+        };
+        let radial = DVec2::new(3e9, 4e9);
+        let circular_speed = circular_orbit_velocity(*MASS_OF_SUN, UomLength::new::<meter>(5e9));
+
+        // Mostly falling in, drifting either way around.
+
+        for drift in [1e3, -1e3] {
+            let velocity = Velocity {
+                x: UomVelocity::new::<meter_per_second>(-3e4 - drift * 4.0),
+                y: UomVelocity::new::<meter_per_second>(-4e4 + drift * 3.0),
+            };
+            let nudged = nudge_to_circular_orbit(&velocity, &position, (&center, *MASS_OF_SUN));
+            let nudged_direction = DVec2::new(nudged.x.value, nudged.y.value);
+
+            assert!(((nudged.scalar() - circular_speed) / circular_speed).value.abs() < 1e-9);
+            assert!(nudged_direction.normalize().dot(radial.normalize()).abs() < 1e-9);
+
+            // Keeps going around the same way.
+
+            assert_eq!(
+                radial.perp_dot(nudged_direction).signum(),
+                radial.perp_dot(DVec2::new(velocity.x.value, velocity.y.value)).signum()
+            );
+        }
+    }
+
+    #[test]
+    fn predict_destination_arrival_times_a_direct_hit() {
+        // At a meter a second, the player first comes within both radii (1.5 m) of the destination 9 m along.
+
+        let arrival = predict_destination_arrival(
+            Entity::from_raw(0),
+            Position::default(),
+            velocity_x(UomVelocity::new::<meter_per_second>(1.0)),
+            &Radius { value: UomLength::new::<meter>(0.5) },
+            &[],
+            &[],
+            &[],
+            (
+                &position_at(UomLength::new::<meter>(10.0)),
+                &Radius { value: UomLength::new::<meter>(1.0) },
+            ),
+            UomTime::new::<second>(1.0),
+            UomLength::new::<meter>(1.0),
+            100,
+        );
+
+        assert_eq!(arrival, Some(UomTime::new::<second>(9.0)));
+    }
+
+    #[test]
+    fn predict_destination_arrival_is_none_for_a_miss() {
+        let arrival = predict_destination_arrival(
+            Entity::from_raw(0),
+            Position::default(),
+            velocity_x(UomVelocity::new::<meter_per_second>(-1.0)),
+            &Radius { value: UomLength::new::<meter>(0.5) },
+            &[],
+            &[],
+            &[],
+            (
+                &position_at(UomLength::new::<meter>(10.0)),
+                &Radius { value: UomLength::new::<meter>(1.0) },
+            ),
+            UomTime::new::<second>(1.0),
+            UomLength::new::<meter>(1.0),
+            100,
+        );
+
+        assert_eq!(arrival, None);
+    }
+
+    #[test]
+    fn gravitational_gamma_breakdown_sums_to_the_total() {
+        let r_s = schwarzschild_radius(*MASS_OF_SUN);
+        let mut masses = sun_at_origin();
+        masses.push((Entity::from_raw(2), position_at(-10.0 * r_s), Mass { value: *MASS_OF_SUN }, None));
+
+        for frame in [GravFrame::AtInfinity, GravFrame::Local] {
+            let (player_terms, observer_terms) = gravitational_gamma_breakdown(
+                frame,
+                Entity::from_raw(0),
+                &position_at(4.0 * r_s),
+                &position_at(2.0 * r_s),
+                &masses,
+                UomLength::new::<meter>(1.0),
+            );
+            let total = gravitational_gamma_in_frame(
+                frame,
+                Entity::from_raw(0),
+                &position_at(4.0 * r_s),
+                &position_at(2.0 * r_s),
+                &masses,
+                UomLength::new::<meter>(1.0),
+            );
+
+            // One term per mass for the player, and for the observer only in the local frame.
+
+            assert_eq!(player_terms.len(), 2);
+            assert_eq!(observer_terms.len(), if frame == GravFrame::Local { 2 } else { 0 });
+            assert_close(
+                gravitational_gamma_from_terms(&player_terms) / gravitational_gamma_from_terms(&observer_terms),
+                total,
+            );
+        }
+    }
+
+    #[test]
+    fn circular_orbit_velocity_matches_real_orbits() {
+        // The Earth around the sun at 1 AU, and the ISS around the Earth at about 400 km up; both are far enough out
+        // that the relativistic adjustment is negligible.
+
+        let earth = circular_orbit_velocity(*MASS_OF_SUN, UomLength::new::<meter>(1.496e11));
+        let iss = circular_orbit_velocity(*MASS_OF_EARTH, UomLength::new::<meter>(6.771e6));
+
+        assert!((earth.get::<kilometer_per_second>() - 29.78).abs() < 0.01, "{:?}", earth);
+        assert!((iss.get::<kilometer_per_second>() - 7.67).abs() < 0.01, "{:?}", iss);
+    }
+
+    #[test]
+    fn relativistic_kinetic_energy_matches_hand_computed_values() {
+        // At 0.6c, γ = 1.25; at 1 km/s, it's the Newtonian ½v² to within rounding.
+
+        let fast = relativistic_kinetic_energy(0.6 * *C);
+        let slow = relativistic_kinetic_energy(UomVelocity::new::<meter_per_second>(1e3));
+
+        assert!((fast / (0.25 * (*C * *C).value) - 1.0).abs() < 1e-12);
+        assert!((slow / 5e5 - 1.0).abs() < 1e-3);
+        assert_eq!(relativistic_kinetic_energy(UomVelocity::default()), 0.0);
+    }
+
+    #[test]
+    fn gravitational_potential_matches_hand_computed_values() {
+        let masses = sun_at_origin();
+        let one_au = UomLength::new::<meter>(1.496e11);
+
+        let potential = gravitational_potential(&position_at(one_au), &masses);
+
+        assert!((potential / -(*G * *MASS_OF_SUN / one_au).value - 1.0).abs() < 1e-12);
+
+        // Nothing at the center itself, or beyond the influence radius.
+
+        assert_eq!(gravitational_potential(&Position::default(), &masses), 0.0);
+
+        let masses = vec![(
+            Entity::from_raw(1),
+            Position::default(),
+            Mass { value: *MASS_OF_SUN },
+            Some(InfluenceRadius { value: one_au / 2.0 }),
+        )];
+
+        assert_eq!(gravitational_potential(&position_at(one_au), &masses), 0.0);
+    }
+
+    #[test]
+    fn total_energy_sums_kinetic_and_potential() {
+        let masses = sun_at_origin();
+        let position = position_at(UomLength::new::<meter>(1.496e11));
+        let velocity = Velocity {
+            y: UomVelocity::new::<meter_per_second>(2.978e4),
+            ..Default::default()
+        };
+
+        // The Earth's orbit is bound.
+
+        let total = total_energy(&velocity, &position, &masses);
+
+        assert_close(
+            total,
+            relativistic_kinetic_energy(velocity.scalar()) + gravitational_potential(&position, &masses),
+        );
+        assert!(total < 0.0);
+    }
+
+    #[test]
+    fn power_curves_map_the_ends_and_are_monotonic() {
+        let curves = [
+            PowerCurve::Linear,
+            PowerCurve::Quadratic,
+            PowerCurve::Exponential { k: 2.0 },
+            PowerCurve::Exponential { k: -2.0 },
+            PowerCurve::Exponential { k: 0.0 },
+        ];
+
+        for curve in curves {
+            assert_close(map_power(curve, 0.0), 0.0);
+            assert_close(map_power(curve, 1.0), 1.0);
+
+            let samples: Vec<_> = (0..=100).map(|step| map_power(curve, step as f64 / 100.0)).collect();
+
+            assert!(samples.windows(2).all(|pair| pair[1] > pair[0]), "{:?} is not monotonic", curve);
+        }
+    }
+}
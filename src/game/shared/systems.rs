@@ -1,49 +1,64 @@
 use crate::{
     game::{
-        destination::Destination,
+        black_hole::BlackHole,
+        destination::{Destination, WaypointProgress},
         object::Planet,
         player::shared::Player,
     },
-    shared::state::{AppState, GameState},
+    shared::state::GameState,
 };
 
 use super::{
-    constants::{C, DAYS_PER_SECOND_UOM, G},
     helpers::{
-        get_translation_from_position, has_collided, length_to_pixel, planet_sprite_pixel_radius_to_scale,
-        rocket_sprite_pixel_radius_to_scale,
+        get_translation_from_position, has_collided, length_to_pixel, planet_sprite_pixel_radius_to_scale, reflect_velocity,
+        rocket_sprite_pixel_radius_to_scale, schwarzschild_radius, verlet_step,
+    },
+    types::{
+        Action, Atmosphere, BoundaryMode, CaptureRadius, CollisionEvent, CollisionKind, CollisionResponse, GravityAffected,
+        GravitySource, InfluenceRadius, KeyBindings, LaunchOrigin, Mass, PhysicsConfig, PlanetSprite, Position, Radius, RocketSprite,
+        RunFailed, TimeCompression, Velocity, VisualRadius, WorldBounds,
     },
-    types::{Mass, PlanetSprite, Position, Radius, RocketSprite, Velocity},
 };
 use bevy::prelude::*;
 use glam::DVec2;
-use uom::si::{acceleration::meter_per_second_squared, f64::Acceleration as UomAcceleration};
+use uom::si::{
+    f64::{Length as UomLength, Velocity as UomVelocity},
+    time::day,
+};
 
-// Escape button.
+// Pause button.
 
-pub fn exit_level_check(
-    keyboard_input: ResMut<Input<KeyCode>>,
-    mut app_state: ResMut<NextState<AppState>>,
-    mut game_state: ResMut<NextState<GameState>>,
+pub fn pause_toggle_check(
+    keyboard_input: Res<Input<KeyCode>>,
+    key_bindings: Res<KeyBindings>,
+    game_state: Res<State<GameState>>,
+    mut next_game_state: ResMut<NextState<GameState>>,
 ) {
-    if keyboard_input.just_pressed(KeyCode::Escape) {
-        app_state.set(AppState::Menu);
-        game_state.set(GameState::Paused);
+    if !key_bindings.just_pressed(&keyboard_input, Action::PauseToggle) {
+        return;
+    }
+
+    match game_state.get() {
+        GameState::Running => next_game_state.set(GameState::SimPaused),
+        GameState::SimPaused => next_game_state.set(GameState::Running),
+        _ => {}
     }
 }
 
 // Basic scale / velocity / position.
 
-pub fn planet_scale_update(mut query: Query<(&mut Transform, &Radius), With<PlanetSprite>>) {
-    for (mut transform, radius) in query.iter_mut() {
-        let scale = planet_sprite_pixel_radius_to_scale(length_to_pixel(radius.value));
+pub fn planet_scale_update(mut query: Query<(&mut Transform, &Radius, Option<&VisualRadius>), With<PlanetSprite>>) {
+    for (mut transform, radius, visual_radius) in query.iter_mut() {
+        let radius = visual_radius.map_or(radius.value, |visual_radius| visual_radius.value);
+        let scale = planet_sprite_pixel_radius_to_scale(length_to_pixel(radius));
         transform.scale = scale;
     }
 }
 
-pub fn rocket_scale_update(mut query: Query<(&mut Transform, &Radius), With<RocketSprite>>) {
-    for (mut transform, radius) in query.iter_mut() {
-        let scale = rocket_sprite_pixel_radius_to_scale(length_to_pixel(radius.value));
+pub fn rocket_scale_update(mut query: Query<(&mut Transform, &Radius, Option<&VisualRadius>), With<RocketSprite>>) {
+    for (mut transform, radius, visual_radius) in query.iter_mut() {
+        let radius = visual_radius.map_or(radius.value, |visual_radius| visual_radius.value);
+        let scale = rocket_sprite_pixel_radius_to_scale(length_to_pixel(radius));
         transform.scale = scale;
     }
 }
@@ -59,67 +74,180 @@ pub fn rocket_rotation_update(mut query: Query<(&mut Transform, &Velocity), With
     }
 }
 
-pub fn position_update(mut query: Query<(&mut Position, &Velocity)>, time: Res<Time>) {
-    for (mut position, velocity) in query.iter_mut() {
-        let time_elapsed = *DAYS_PER_SECOND_UOM * time.delta_seconds() as f64;
-
-        position.x += velocity.x * time_elapsed;
-        position.y += velocity.y * time_elapsed;
-    }
-}
-
 pub fn translation_update(mut query: Query<(&mut Transform, &Position)>) {
     for (mut transform, position) in query.iter_mut() {
         transform.translation = get_translation_from_position(position);
     }
 }
 
-// Velocity based on gravitation.
+// Motion based on gravitation (velocity-Verlet).
 
-pub fn velocity_update(mut query: Query<(&mut Velocity, Entity, &Position)>, masses: Query<(Entity, &Position, &Mass)>, time: Res<Time>) {
-    let time_elapsed = *DAYS_PER_SECOND_UOM * time.delta_seconds() as f64;
+#[allow(clippy::type_complexity)]
+pub fn integrate_motion(
+    mut queries: ParamSet<(
+        Query<(Entity, &Position, &Mass, Option<&InfluenceRadius>), With<GravitySource>>,
+        Query<(Entity, &Position, &Atmosphere)>,
+        Query<(Entity, &mut Position, &mut Velocity, Option<&Radius>), With<GravityAffected>>,
+        Query<(Entity, &Position, &Radius, Option<&CaptureRadius>, Option<&CollisionResponse>), Or<(With<Planet>, With<Destination>)>>,
+        Query<(Entity, &Position, &Mass), With<BlackHole>>,
+    )>,
+    fixed_time: Res<FixedTime>,
+    time_compression: Res<TimeCompression>,
+    physics_config: Res<PhysicsConfig>,
+) {
+    let time_elapsed = time_compression.time_elapsed(fixed_time.period.as_secs_f64());
 
-    for (mut velocity, entity, position) in query.iter_mut() {
-        if velocity.x.value == 0.0 || velocity.y.value == 0.0 {
-            continue;
+    // Split large steps (e.g., from a high time compression) to keep the integration stable.
+
+    let substeps = physics_config.substeps(time_elapsed);
+    let substep_time = time_elapsed / substeps as f64;
+
+    let masses: Vec<_> = queries
+        .p0()
+        .iter()
+        .map(|(entity, position, mass, influence_radius)| (entity, *position, *mass, influence_radius.copied()))
+        .collect();
+    let atmospheres: Vec<_> = queries
+        .p1()
+        .iter()
+        .map(|(entity, position, atmosphere)| (entity, *position, *atmosphere))
+        .collect();
+
+    // Everything `collision_check` reacts to, as a body and the radius at which it's touched.
+
+    let mut contacts: Vec<_> = queries
+        .p3()
+        .iter()
+        .filter(|(.., collision_response)| *collision_response != Some(&CollisionResponse::Ignore))
+        .map(|(entity, position, radius, capture_radius, _)| (entity, *position, CaptureRadius::or_radius(capture_radius, radius)))
+        .collect();
+
+    contacts.extend(queries.p4().iter().map(|(entity, position, mass)| {
+        (
+            entity,
+            *position,
+            Radius {
+                value: schwarzschild_radius(mass.value),
+            },
+        )
+    }));
+
+    // Each body only reads the mass snapshot, so they can be integrated in parallel.
+
+    queries.p2().par_iter_mut().for_each_mut(|(entity, mut position, mut velocity, radius)| {
+        if velocity.x.value == 0.0 && velocity.y.value == 0.0 {
+            return;
         }
 
-        let mut total_gravitational_acceleration_x = UomAcceleration::new::<meter_per_second_squared>(0.0);
-        let mut total_gravitational_acceleration_y = UomAcceleration::new::<meter_per_second_squared>(0.0);
+        for _ in 0..substeps {
+            verlet_step(
+                entity,
+                &mut position,
+                &mut velocity,
+                &masses,
+                substep_time,
+                physics_config.softening,
+            );
 
-        for (other_entity, other_position, other_mass) in masses.iter() {
-            if entity == other_entity {
+            // Stop at the first contact, so a fast body can't pass clean through something within a single tick; the
+            // collision checks that follow pick it up from there.
+
+            let Some(radius) = radius else {
                 continue;
+            };
+
+            let is_touching = contacts.iter().any(|(other_entity, other_position, other_radius)| {
+                *other_entity != entity && has_collided((&position, radius), (other_position, other_radius))
+            });
+
+            if is_touching {
+                break;
             }
+        }
 
-            let direction = DVec2::new((other_position.x - position.x).value, (other_position.y - position.y).value);
-            let direction = direction.normalize();
+        // Atmospheric drag.
+
+        for (other_entity, other_position, atmosphere) in atmospheres.iter() {
+            if entity == *other_entity {
+                continue;
+            }
 
             let delta_x = position.x - other_position.x;
             let delta_y = position.y - other_position.y;
-            let distance_squared = delta_x * delta_x + delta_y * delta_y;
-            let distance = distance_squared.sqrt();
+            let distance = (delta_x * delta_x + delta_y * delta_y).sqrt();
+
+            if distance > atmosphere.outer_radius {
+                continue;
+            }
+
+            let drag_factor = (-atmosphere.drag_coeff * time_elapsed.get::<day>()).exp();
+
+            velocity.x *= drag_factor;
+            velocity.y *= drag_factor;
+        }
+    });
+}
+
+// World bounds.
+
+pub fn launch_origin_record(mut launch_origin: ResMut<LaunchOrigin>, query: Query<&Position, With<Player>>) {
+    if let Ok(position) = query.get_single() {
+        launch_origin.0 = *position;
+    }
+}
 
-            let gravitational_acceleration = (*G * other_mass.value) / distance_squared;
+pub fn boundary_check(
+    world_bounds: Res<WorldBounds>,
+    launch_origin: Res<LaunchOrigin>,
+    mut query: Query<(&mut Position, &mut Velocity), With<Player>>,
+    mut run_failed_events: EventWriter<RunFailed>,
+) {
+    let Ok((mut position, mut velocity)) = query.get_single_mut() else {
+        return;
+    };
+
+    let zero = UomLength::default();
+    let is_outside = position.x < zero || position.x > world_bounds.width || position.y < zero || position.y > world_bounds.height;
 
-            let mut relativistic_adjustment = 1.0 - (2.0 * *G * other_mass.value / (*C * *C * distance)).value;
+    if !is_outside {
+        return;
+    }
 
-            if relativistic_adjustment <= 0.0 {
-                relativistic_adjustment = 0.0;
+    match world_bounds.mode {
+        BoundaryMode::None => {}
+        BoundaryMode::Clamp => {
+            // Stop at the edge by removing the outward component of the velocity.
+
+            if position.x < zero || position.x > world_bounds.width {
+                position.x = position.x.max(zero).min(world_bounds.width);
+                velocity.x = UomVelocity::default();
             }
 
-            let gravitational_acceleration_x = direction.x * gravitational_acceleration * relativistic_adjustment;
-            let gravitational_acceleration_y = direction.y * gravitational_acceleration * relativistic_adjustment;
+            if position.y < zero || position.y > world_bounds.height {
+                position.y = position.y.max(zero).min(world_bounds.height);
+                velocity.y = UomVelocity::default();
+            }
+        }
+        BoundaryMode::Wrap => {
+            if position.x < zero {
+                position.x += world_bounds.width;
+            } else if position.x > world_bounds.width {
+                position.x -= world_bounds.width;
+            }
 
-            total_gravitational_acceleration_x += gravitational_acceleration_x;
-            total_gravitational_acceleration_y += gravitational_acceleration_y;
+            if position.y < zero {
+                position.y += world_bounds.height;
+            } else if position.y > world_bounds.height {
+                position.y -= world_bounds.height;
+            }
         }
+        BoundaryMode::FailOnExit => {
+            // Put the player back where it launched from, ready for another try.
 
-        velocity.x += total_gravitational_acceleration_x * time_elapsed;
-        velocity.y += total_gravitational_acceleration_y * time_elapsed;
+            *position = launch_origin.0;
+            *velocity = Velocity::default();
 
-        if velocity.scalar() > *C {
-            println!("AHHHHHHHHHHHHHH...fuck.");
+            run_failed_events.send(RunFailed { reason: "out of bounds" });
         }
     }
 }
@@ -127,23 +255,121 @@ pub fn velocity_update(mut query: Query<(&mut Velocity, Entity, &Position)>, mas
 // Collisions.
 
 pub fn collision_check(
-    player_query: Query<(&Position, &Radius), With<Player>>,
-    planet_query: Query<(&Position, &Radius), With<Planet>>,
-    destination_query: Query<(&Position, &Radius), With<Destination>>,
-    mut game_state: ResMut<NextState<GameState>>,
+    player_query: Query<(Entity, &Position, &Radius), With<Player>>,
+    planet_query: Query<(Entity, &Position, &Radius, &CollisionResponse), With<Planet>>,
+    black_hole_query: Query<(Entity, &Position, &Mass), With<BlackHole>>,
+    destination_query: Query<(Entity, &Position, &Radius, Option<&CaptureRadius>), With<Destination>>,
+    mut collision_events: EventWriter<CollisionEvent>,
 ) {
-    let (player_position, player_radius) = player_query.single();
-    let (destination_position, destination_radius) = destination_query.single();
+    let (player_entity, player_position, player_radius) = player_query.single();
 
-    if has_collided((player_position, player_radius), (destination_position, destination_radius)) {
-        game_state.set(GameState::Finished);
-        println!("success!");
+    for (destination_entity, destination_position, destination_radius, capture_radius) in destination_query.iter() {
+        let capture_radius = CaptureRadius::or_radius(capture_radius, destination_radius);
+
+        if has_collided((player_position, player_radius), (destination_position, &capture_radius)) {
+            collision_events.send(CollisionEvent {
+                player: player_entity,
+                other: destination_entity,
+                kind: CollisionKind::Destination,
+            });
+        }
     }
 
-    for (planet_position, planet_radius) in planet_query.iter() {
+    for (planet_entity, planet_position, planet_radius, collision_response) in planet_query.iter() {
+        if *collision_response == CollisionResponse::Ignore {
+            continue;
+        }
+
         if has_collided((player_position, player_radius), (planet_position, planet_radius)) {
-            game_state.set(GameState::Paused);
-            println!("failed!");
+            collision_events.send(CollisionEvent {
+                player: player_entity,
+                other: planet_entity,
+                kind: CollisionKind::Planet,
+            });
+        }
+    }
+
+    // Crossing a black hole's event horizon captures the player, whatever the sprite's size. Planets are left to their
+    // collision response, since the inflated masses put their horizons well outside their surfaces.
+
+    for (black_hole_entity, black_hole_position, black_hole_mass) in black_hole_query.iter() {
+        let delta_x = player_position.x - black_hole_position.x;
+        let delta_y = player_position.y - black_hole_position.y;
+
+        if (delta_x * delta_x + delta_y * delta_y).sqrt() <= schwarzschild_radius(black_hole_mass.value) {
+            collision_events.send(CollisionEvent {
+                player: player_entity,
+                other: black_hole_entity,
+                kind: CollisionKind::Horizon,
+            });
+        }
+    }
+}
+
+#[allow(clippy::type_complexity)]
+pub fn handle_collision_events(
+    mut collision_events: EventReader<CollisionEvent>,
+    mut game_state: ResMut<NextState<GameState>>,
+    mut waypoint_progress: ResMut<WaypointProgress>,
+    destination_query: Query<&Destination>,
+    mut player_query: Query<(&mut Position, &mut Velocity, &Radius), With<Player>>,
+    planet_query: Query<(&Position, &Radius, &CollisionResponse), (With<Planet>, Without<Player>)>,
+) {
+    let waypoint_count = destination_query
+        .iter()
+        .filter(|destination| matches!(destination, Destination::Waypoint { .. }))
+        .count() as u32;
+
+    for collision_event in collision_events.iter() {
+        match collision_event.kind {
+            CollisionKind::Destination => {
+                let Ok(destination) = destination_query.get(collision_event.other) else {
+                    continue;
+                };
+
+                if waypoint_progress.visit(*destination, waypoint_count) {
+                    game_state.set(GameState::Finished);
+                    println!("success!");
+                }
+            }
+            CollisionKind::Planet => {
+                let Ok((planet_position, planet_radius, collision_response)) = planet_query.get(collision_event.other) else {
+                    continue;
+                };
+
+                match collision_response {
+                    CollisionResponse::Fail => {
+                        game_state.set(GameState::Paused);
+                        println!("failed!");
+                    }
+                    CollisionResponse::Bounce { restitution } => {
+                        let Ok((mut player_position, mut player_velocity, player_radius)) = player_query.get_mut(collision_event.player)
+                        else {
+                            continue;
+                        };
+
+                        let normal = DVec2::new(
+                            (player_position.x - planet_position.x).value,
+                            (player_position.y - planet_position.y).value,
+                        )
+                        .normalize_or_zero();
+
+                        *player_velocity = reflect_velocity(&player_velocity, normal, *restitution as f64);
+
+                        // Push the player back to the surface, so the same contact isn't handled again next step.
+
+                        let surface_distance = planet_radius.value + player_radius.value;
+
+                        player_position.x = planet_position.x + surface_distance * normal.x;
+                        player_position.y = planet_position.y + surface_distance * normal.y;
+                    }
+                    CollisionResponse::Ignore => {}
+                }
+            }
+            CollisionKind::Horizon => {
+                game_state.set(GameState::Paused);
+                println!("failed!");
+            }
         }
     }
 }
@@ -1,5 +1,15 @@
-use bevy::prelude::*;
-use uom::si::f64::{Length as UomLength, Mass as UomMass, Time as UomTime, Velocity as UomVelocity};
+use bevy::{prelude::*, utils::HashMap};
+use rand::SeedableRng;
+use rand_chacha::ChaCha8Rng;
+use serde::{Deserialize, Serialize};
+use uom::si::{
+    f64::{Length as UomLength, Mass as UomMass, Time as UomTime, Velocity as UomVelocity},
+    time::day,
+};
+
+use super::constants::{
+    DAYS_PER_SECOND, MASS_FACTOR, MAX_PHYSICS_SUBSTEPS, PHYSICS_TIMESTEP_SECONDS, SCREEN_HEIGHT_UOM, SCREEN_WIDTH_UOM, UNIT_RADIUS,
+};
 
 #[derive(Component, Default)]
 pub struct GameItem;
@@ -11,22 +21,60 @@ pub struct PlanetSprite;
 pub struct RocketSprite;
 
 #[derive(Component, Default)]
+pub struct GravitySource;
+
+#[derive(Component, Default)]
+pub struct GravityAffected;
+
+#[derive(Component, Default, Clone, Copy, Debug, PartialEq)]
 pub struct Position {
     pub x: UomLength,
     pub y: UomLength,
 }
 
-#[derive(Component, Default)]
+#[derive(Component, Default, Clone, Copy)]
 pub struct Radius {
     pub value: UomLength,
 }
 
-#[derive(Component, Default)]
+/// The radius within which a destination counts as reached, when it should differ from its `Radius`.
+#[derive(Component, Default, Clone, Copy)]
+pub struct CaptureRadius {
+    pub value: UomLength,
+}
+
+impl CaptureRadius {
+    /// The radius to capture within: this one if present, otherwise the body's own.
+    pub fn or_radius(capture_radius: Option<&CaptureRadius>, radius: &Radius) -> Radius {
+        capture_radius.map_or(*radius, |capture_radius| Radius { value: capture_radius.value })
+    }
+}
+
+/// The radius a body is drawn at, when it should differ from its physical `Radius`.
+#[derive(Component, Default, Clone, Copy)]
+pub struct VisualRadius {
+    pub value: UomLength,
+}
+
+#[derive(Component, Default, Clone, Copy)]
 pub struct Mass {
     pub value: UomMass,
 }
 
-#[derive(Component, Default)]
+/// Distance beyond which a mass no longer pulls on anything; masses without one reach everywhere.
+#[derive(Component, Clone, Copy)]
+pub struct InfluenceRadius {
+    pub value: UomLength,
+}
+
+#[derive(Component, Default, Clone, Copy)]
+pub struct Atmosphere {
+    pub outer_radius: UomLength,
+    /// Drag rate, per simulated day, while inside the band: velocity decays by `exp(-drag_coeff * days)`.
+    pub drag_coeff: f64,
+}
+
+#[derive(Component, Default, Clone, Copy, Debug, PartialEq)]
 pub struct Velocity {
     pub x: UomVelocity,
     pub y: UomVelocity,
@@ -36,6 +84,252 @@ impl Velocity {
     pub fn scalar(&self) -> UomVelocity {
         (self.x * self.x + self.y * self.y).sqrt()
     }
+
+    pub fn clamp_to_speed(&mut self, max: UomVelocity) -> bool {
+        let speed = self.scalar();
+
+        if speed <= max {
+            return false;
+        }
+
+        let scale = (max / speed).value;
+
+        self.x *= scale;
+        self.y *= scale;
+
+        true
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum BoundaryMode {
+    #[default]
+    None,
+    Clamp,
+    Wrap,
+    FailOnExit,
+}
+
+/// Where the current run launched from, so a failed run can put the player back; the local `GravFrame` observer hovers
+/// here.
+#[derive(Resource, Default, Clone, Copy)]
+pub struct LaunchOrigin(pub Position);
+
+/// Maps launch power (drag distance or keyboard power, in `[0, 1]`) to a fraction of the maximum launch speed.
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, Default, PartialEq)]
+pub enum PowerCurve {
+    #[default]
+    Linear,
+    Quadratic,
+    Exponential {
+        k: f32,
+    },
+}
+
+#[derive(Resource)]
+pub struct WorldBounds {
+    pub mode: BoundaryMode,
+    pub width: UomLength,
+    pub height: UomLength,
+}
+
+impl Default for WorldBounds {
+    fn default() -> Self {
+        Self {
+            mode: BoundaryMode::default(),
+            width: *SCREEN_WIDTH_UOM,
+            height: *SCREEN_HEIGHT_UOM,
+        }
+    }
+}
+
+#[derive(Resource, Clone, Debug, PartialEq)]
+pub struct VisualsConfig {
+    pub length_contraction: bool,
+    pub potential_cross_section: bool,
+    pub time_dilation_heatmap: bool,
+    pub time_dilation_heatmap_opacity: f32,
+    pub orbit_trace: bool,
+    pub field_arrows: bool,
+}
+
+impl Default for VisualsConfig {
+    fn default() -> Self {
+        Self {
+            length_contraction: true,
+            potential_cross_section: false,
+            time_dilation_heatmap: false,
+            time_dilation_heatmap_opacity: 0.3,
+            orbit_trace: false,
+            field_arrows: false,
+        }
+    }
+}
+
+/// Simulated days that pass per real second.
+#[derive(Resource, Clone, Copy, Debug, PartialEq)]
+pub struct TimeCompression(pub f64);
+
+impl Default for TimeCompression {
+    fn default() -> Self {
+        Self(DAYS_PER_SECOND)
+    }
+}
+
+impl TimeCompression {
+    pub fn time_elapsed(&self, seconds: f64) -> UomTime {
+        UomTime::new::<day>(self.0 * seconds)
+    }
+}
+
+/// Inflates real masses so gravity is strong enough to play with.
+#[derive(Resource, Clone, Copy, Debug, PartialEq)]
+pub struct GameplayScale {
+    pub mass_factor: f64,
+}
+
+impl Default for GameplayScale {
+    fn default() -> Self {
+        Self { mass_factor: MASS_FACTOR }
+    }
+}
+
+impl GameplayScale {
+    pub fn mass(&self, mass: UomMass) -> Mass {
+        Mass { value: mass * self.mass_factor }
+    }
+}
+
+#[derive(Resource, Clone, Copy, Debug, PartialEq)]
+pub struct PhysicsConfig {
+    /// Largest simulated step, in days, before a physics tick is split into equal substeps.
+    pub max_step_days: f64,
+    /// Floor on the distance used in the gravitational math (the softening length, ε).
+    pub softening: UomLength,
+}
+
+impl Default for PhysicsConfig {
+    fn default() -> Self {
+        Self {
+            max_step_days: DAYS_PER_SECOND * PHYSICS_TIMESTEP_SECONDS as f64,
+            softening: *UNIT_RADIUS / 100.0,
+        }
+    }
+}
+
+impl PhysicsConfig {
+    /// How many substeps to split `time_elapsed` into; a non-positive `max_step_days` disables splitting, and the count
+    /// is capped at `MAX_PHYSICS_SUBSTEPS` so a tiny one can't stall the game.
+    pub fn substeps(&self, time_elapsed: UomTime) -> usize {
+        if self.max_step_days.is_nan() || self.max_step_days <= 0.0 {
+            return 1;
+        }
+
+        // Allow a little slack, so rounding in the fixed timestep doesn't add a spurious substep.
+
+        (time_elapsed.get::<day>() / self.max_step_days - 1e-6)
+            .ceil()
+            .clamp(1.0, MAX_PHYSICS_SUBSTEPS as f64) as usize
+    }
+}
+
+#[derive(Resource, Serialize, Deserialize, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum ClockDisplayMode {
+    #[default]
+    Both,
+    Difference,
+}
+
+/// Seeds every random element, so runs and screenshots are reproducible.
+#[derive(Resource, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct SimSeed(pub u64);
+
+/// The random stream for gameplay effects, reseeded from `SimSeed` whenever a level spawns; ChaCha8, unlike `StdRng`,
+/// gives the same stream across rand versions.
+#[derive(Resource)]
+pub struct SimRng(pub ChaCha8Rng);
+
+impl SimRng {
+    pub fn new(seed: SimSeed) -> Self {
+        Self(ChaCha8Rng::seed_from_u64(seed.0))
+    }
+}
+
+impl FromWorld for SimRng {
+    fn from_world(world: &mut World) -> Self {
+        Self::new(world.get_resource::<SimSeed>().copied().unwrap_or_default())
+    }
+}
+
+/// The units the HUD reads out in: km/s and days, or fractions of c and years.
+#[derive(Resource, Serialize, Deserialize, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum UnitSystem {
+    #[default]
+    SI,
+    Natural,
+}
+
+#[derive(Resource)]
+pub struct HudConfig {
+    /// Ease displayed numbers toward their true values, rather than snapping each frame.
+    pub smooth: bool,
+    /// Easing rate, per second; higher catches up faster.
+    pub smoothing_rate: f64,
+}
+
+impl Default for HudConfig {
+    fn default() -> Self {
+        Self { smooth: false, smoothing_rate: 10.0 }
+    }
+}
+
+/// Which stationary observer the player's gravitational dilation is measured against.
+#[derive(Resource, Serialize, Deserialize, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum GravFrame {
+    #[default]
+    AtInfinity,
+    /// Hovering at the launch point.
+    Local,
+}
+
+impl GravFrame {
+    pub fn label(&self) -> &'static str {
+        match self {
+            GravFrame::AtInfinity => "γ_g",
+            GravFrame::Local => "γ_g,loc",
+        }
+    }
+}
+
+/// What happens when the player touches a planet.
+#[derive(Component, Serialize, Deserialize, Clone, Copy, Debug, Default, PartialEq)]
+pub enum CollisionResponse {
+    #[default]
+    Fail,
+    Bounce {
+        restitution: f32,
+    },
+    Ignore,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum CollisionKind {
+    Planet,
+    Destination,
+    Horizon,
+}
+
+#[derive(Event)]
+pub struct CollisionEvent {
+    pub player: Entity,
+    pub other: Entity,
+    pub kind: CollisionKind,
+}
+
+/// Sent when a run fails short of a collision, to pause it and say why.
+#[derive(Event)]
+pub struct RunFailed {
+    pub reason: &'static str,
 }
 
 #[derive(Component, Default)]
@@ -52,3 +346,179 @@ pub struct VelocityGamma {
 pub struct GravitationalGamma {
     pub value: f64,
 }
+
+/// Logical inputs, so systems ask for an action rather than a literal key.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Action {
+    PauseToggle,
+    PauseMenu,
+    Restart,
+    NextLevel,
+    CyclePreviousLevel,
+    CycleNextLevel,
+    Launch,
+    AimLeft,
+    AimRight,
+    PowerUp,
+    PowerDown,
+    ThrustUp,
+    ThrustDown,
+    ThrustLeft,
+    ThrustRight,
+    ToggleMute,
+    ToggleClockDisplay,
+    ToggleUnits,
+    ToggleGravFrame,
+    ToggleCameraFollow,
+    TogglePotential,
+    ToggleHeatmap,
+    ToggleOrbitTrace,
+    ToggleFieldArrows,
+    ToggleLegend,
+    ToggleOrbitInsertion,
+    SnapOrbitInsertion,
+    NudgeOrbit,
+    ToggleGammaBreakdown,
+    ToggleSubsteps,
+    Save,
+    Load,
+}
+
+/// The keys bound to each `Action`; any of them triggers it.
+#[derive(Resource, Clone, Debug)]
+pub struct KeyBindings {
+    pub keys: HashMap<Action, Vec<KeyCode>>,
+}
+
+impl KeyBindings {
+    pub fn bind(&mut self, action: Action, keys: impl IntoIterator<Item = KeyCode>) {
+        self.keys.insert(action, keys.into_iter().collect());
+    }
+
+    pub fn pressed(&self, keyboard_input: &Input<KeyCode>, action: Action) -> bool {
+        self.keys_for(action).iter().any(|key| keyboard_input.pressed(*key))
+    }
+
+    pub fn just_pressed(&self, keyboard_input: &Input<KeyCode>, action: Action) -> bool {
+        self.keys_for(action).iter().any(|key| keyboard_input.just_pressed(*key))
+    }
+
+    fn keys_for(&self, action: Action) -> &[KeyCode] {
+        self.keys.get(&action).map_or(&[], Vec::as_slice)
+    }
+}
+
+impl Default for KeyBindings {
+    fn default() -> Self {
+        let keys = [
+            (Action::PauseToggle, vec![KeyCode::Space]),
+            (Action::PauseMenu, vec![KeyCode::Escape]),
+            (Action::Restart, vec![KeyCode::R]),
+            (Action::NextLevel, vec![KeyCode::N]),
+            (Action::CyclePreviousLevel, vec![KeyCode::BracketLeft]),
+            (Action::CycleNextLevel, vec![KeyCode::BracketRight]),
+            (Action::Launch, vec![KeyCode::Return]),
+            (Action::AimLeft, vec![KeyCode::Left]),
+            (Action::AimRight, vec![KeyCode::Right]),
+            (Action::PowerUp, vec![KeyCode::Up]),
+            (Action::PowerDown, vec![KeyCode::Down]),
+            (Action::ThrustUp, vec![KeyCode::W, KeyCode::Up]),
+            (Action::ThrustDown, vec![KeyCode::S, KeyCode::Down]),
+            (Action::ThrustLeft, vec![KeyCode::A, KeyCode::Left]),
+            (Action::ThrustRight, vec![KeyCode::D, KeyCode::Right]),
+            (Action::ToggleMute, vec![KeyCode::M]),
+            (Action::ToggleClockDisplay, vec![KeyCode::C]),
+            (Action::ToggleUnits, vec![KeyCode::U]),
+            (Action::ToggleGravFrame, vec![KeyCode::I]),
+            (Action::ToggleCameraFollow, vec![KeyCode::F]),
+            (Action::TogglePotential, vec![KeyCode::P]),
+            (Action::ToggleHeatmap, vec![KeyCode::G]),
+            (Action::ToggleOrbitTrace, vec![KeyCode::O]),
+            (Action::ToggleFieldArrows, vec![KeyCode::V]),
+            (Action::ToggleLegend, vec![KeyCode::H]),
+            (Action::ToggleOrbitInsertion, vec![KeyCode::K]),
+            (Action::SnapOrbitInsertion, vec![KeyCode::ShiftLeft]),
+            (Action::NudgeOrbit, vec![KeyCode::J]),
+            (Action::ToggleGammaBreakdown, vec![KeyCode::F3]),
+            (Action::ToggleSubsteps, vec![KeyCode::F4]),
+            (Action::Save, vec![KeyCode::F5]),
+            (Action::Load, vec![KeyCode::F9]),
+        ];
+
+        Self { keys: keys.into_iter().collect() }
+    }
+}
+
+/// Where a launch ends up, per a forward simulation from the current aim.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum LaunchOutcome {
+    Hit,
+    CrashInto(Entity),
+    Escaped,
+    Orbiting,
+}
+
+impl LaunchOutcome {
+    pub fn label(&self) -> &'static str {
+        match self {
+            LaunchOutcome::Hit => "hit",
+            LaunchOutcome::CrashInto(_) => "crash",
+            LaunchOutcome::Escaped => "escape",
+            LaunchOutcome::Orbiting => "orbit",
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use uom::si::velocity::meter_per_second;
+
+    fn velocity(x: f64, y: f64) -> Velocity {
+        Velocity {
+            x: UomVelocity::new::<meter_per_second>(x),
+            y: UomVelocity::new::<meter_per_second>(y),
+        }
+    }
+
+    fn max() -> UomVelocity {
+        UomVelocity::new::<meter_per_second>(5.0)
+    }
+
+    #[test]
+    fn clamp_to_speed_leaves_a_slower_velocity_alone() {
+        let mut slow = velocity(3.0, 2.0);
+
+        assert!(!slow.clamp_to_speed(max()));
+        assert_eq!(slow, velocity(3.0, 2.0));
+    }
+
+    #[test]
+    fn clamp_to_speed_leaves_a_velocity_at_the_cap_alone() {
+        let mut at_cap = velocity(3.0, 4.0);
+
+        assert!(!at_cap.clamp_to_speed(max()));
+        assert_eq!(at_cap, velocity(3.0, 4.0));
+    }
+
+    #[test]
+    fn clamp_to_speed_rescales_a_faster_velocity_to_the_cap() {
+        let mut fast = velocity(6.0, 8.0);
+
+        assert!(fast.clamp_to_speed(max()));
+        assert!((fast.scalar() - max()).abs().value < 1e-12);
+
+        // Same direction, just shorter.
+
+        assert!((fast.x.value - 3.0).abs() < 1e-12);
+        assert!((fast.y.value - 4.0).abs() < 1e-12);
+    }
+
+    #[test]
+    fn clamp_to_speed_is_a_no_op_at_rest() {
+        let mut rest = Velocity::default();
+
+        assert!(!rest.clamp_to_speed(max()));
+        assert_eq!(rest, Velocity::default());
+    }
+}
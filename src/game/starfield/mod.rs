@@ -0,0 +1,207 @@
+use bevy::prelude::*;
+use rand::{Rng, SeedableRng};
+use rand_chacha::ChaCha8Rng;
+
+use crate::shared::{SCREEN_HEIGHT_PX, SCREEN_WIDTH_PX};
+
+use super::{
+    camera::home_translation,
+    player::shared::Player,
+    shared::{
+        constants::C,
+        types::{SimSeed, Velocity},
+    },
+};
+
+const STAR_SIZE_PX: f32 = 2.0;
+const STAR_Z: f32 = -1.0;
+
+// Components / bundles / resources.
+
+#[derive(Resource)]
+pub struct StarfieldConfig {
+    /// Stars per layer.
+    pub density: usize,
+    pub layers: usize,
+    /// How far the nearest layer shifts against camera movement, as a fraction of that movement.
+    pub max_parallax: f32,
+}
+
+impl Default for StarfieldConfig {
+    fn default() -> Self {
+        Self {
+            density: 70,
+            layers: 3,
+            max_parallax: 0.3,
+        }
+    }
+}
+
+#[derive(Component, Default)]
+pub struct Star {
+    /// Rest-frame direction from the view center.
+    pub direction: Vec2,
+    /// Pixels from the view center.
+    pub distance: f32,
+    /// Fraction of camera movement the star lags by; nearer layers lag more.
+    pub parallax: f32,
+}
+
+#[derive(Bundle, Default)]
+pub struct StarBundle {
+    pub star: Star,
+    pub sprite: SpriteBundle,
+}
+
+// Startup systems.
+
+pub fn spawn_starfield(mut commands: Commands, starfield_config: Res<StarfieldConfig>, sim_seed: Res<SimSeed>) {
+    // Draw from a stream of our own, so the stars come out the same whatever else consumed `SimRng`.
+
+    let mut rng = ChaCha8Rng::seed_from_u64(sim_seed.0);
+    let max_distance = Vec2::new(SCREEN_WIDTH_PX as f32, SCREEN_HEIGHT_PX as f32).length() / 2.0;
+    let center = home_translation();
+
+    for layer in 0..starfield_config.layers {
+        // The last layer is the nearest: the largest, brightest, and fastest to shift.
+
+        let nearness = (layer + 1) as f32 / starfield_config.layers as f32;
+        let parallax = starfield_config.max_parallax * nearness;
+
+        for _ in 0..starfield_config.density {
+            let direction = Vec2::from_angle(rng.gen_range(0.0..std::f32::consts::TAU));
+            let distance = rng.gen_range(0.0..max_distance);
+
+            commands.spawn(StarBundle {
+                star: Star { direction, distance, parallax },
+                sprite: SpriteBundle {
+                    sprite: Sprite {
+                        color: Color::rgba(1.0, 1.0, 1.0, rng.gen_range(0.3..0.8) * (0.5 + 0.5 * nearness)),
+                        custom_size: Some(Vec2::splat(STAR_SIZE_PX * (0.5 + nearness))),
+                        ..Default::default()
+                    },
+                    transform: Transform::from_translation((center + direction * distance).extend(STAR_Z)),
+                    ..Default::default()
+                },
+            });
+        }
+    }
+}
+
+pub fn despawn_starfield(mut commands: Commands, query: Query<Entity, With<Star>>) {
+    for entity in query.iter() {
+        commands.entity(entity).despawn_recursive();
+    }
+}
+
+// Systems.
+
+pub fn aberration_update(
+    mut star_query: Query<(&mut Transform, &Star)>,
+    camera_query: Query<&Transform, (With<Camera>, Without<Star>)>,
+    player_query: Query<&Velocity, With<Player>>,
+) {
+    let Ok(camera_transform) = camera_query.get_single() else {
+        return;
+    };
+
+    let Ok(player_velocity) = player_query.get_single() else {
+        return;
+    };
+
+    let beta = (player_velocity.scalar() / *C).value;
+    let travel_direction = Vec2::new(player_velocity.x.value as f32, player_velocity.y.value as f32).normalize_or_zero();
+    let center = camera_transform.translation.truncate();
+
+    let camera_offset = center - home_translation();
+
+    for (mut transform, star) in star_query.iter_mut() {
+        let direction = aberrate_direction(star.direction, beta, travel_direction);
+        let offset = parallax_offset(direction * star.distance, camera_offset, star.parallax);
+
+        transform.translation = (center + offset).extend(STAR_Z);
+    }
+}
+
+// Helpers.
+
+/// Shifts a star's offset from the view center against the camera's movement, wrapping it around the screen so
+/// the layer never runs out of stars.
+pub fn parallax_offset(offset: Vec2, camera_offset: Vec2, parallax: f32) -> Vec2 {
+    let size = Vec2::new(SCREEN_WIDTH_PX as f32, SCREEN_HEIGHT_PX as f32);
+    let shifted = offset - camera_offset * parallax + size / 2.0;
+
+    Vec2::new(shifted.x.rem_euclid(size.x), shifted.y.rem_euclid(size.y)) - size / 2.0
+}
+
+pub fn aberrate_direction(direction: Vec2, beta: f64, travel_direction: Vec2) -> Vec2 {
+    if beta <= 0.0 || travel_direction == Vec2::ZERO {
+        return direction;
+    }
+
+    // cos θ' = (cos θ + β) / (1 + β cos θ), keeping the star on the same side of the travel direction.
+
+    let cos_theta = direction.dot(travel_direction).clamp(-1.0, 1.0) as f64;
+    let cos_theta_prime = (cos_theta + beta) / (1.0 + beta * cos_theta);
+    let theta_prime = cos_theta_prime.clamp(-1.0, 1.0).acos() as f32;
+
+    let side = if travel_direction.perp_dot(direction) < 0.0 { -1.0 } else { 1.0 };
+
+    Vec2::from_angle(side * theta_prime).rotate(travel_direction)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn starfield(seed: u64) -> Vec<(Vec2, f32, f32, Color)> {
+        let mut world = World::new();
+
+        world.init_resource::<StarfieldConfig>();
+        world.insert_resource(SimSeed(seed));
+
+        let mut schedule = Schedule::default();
+        schedule.add_systems(spawn_starfield);
+        schedule.run(&mut world);
+
+        world
+            .query::<(&Star, &Sprite)>()
+            .iter(&world)
+            .map(|(star, sprite)| (star.direction, star.distance, star.parallax, sprite.color))
+            .collect()
+    }
+
+    #[test]
+    fn starfield_is_the_same_for_the_same_seed() {
+        let stars = starfield(7);
+
+        assert_eq!(stars.len(), StarfieldConfig::default().density * StarfieldConfig::default().layers);
+        assert_eq!(stars, starfield(7));
+        assert_ne!(stars, starfield(8));
+    }
+
+    #[test]
+    fn aberrate_direction_at_rest_is_unchanged() {
+        for direction in [Vec2::X, Vec2::Y, Vec2::new(-0.6, 0.8)] {
+            assert_eq!(aberrate_direction(direction, 0.0, Vec2::X), direction);
+        }
+    }
+
+    #[test]
+    fn aberrate_direction_bunches_stars_toward_the_travel_direction() {
+        for direction in [Vec2::Y, Vec2::NEG_Y, Vec2::new(-0.6, 0.8), Vec2::new(0.6, -0.8)] {
+            let aberrated = aberrate_direction(direction, 0.9, Vec2::X);
+
+            assert!(aberrated.dot(Vec2::X) > direction.dot(Vec2::X));
+            assert!((aberrated.length() - 1.0).abs() < 1e-6);
+
+            // Stars stay on their own side of the travel direction.
+
+            assert_eq!(aberrated.y.signum(), direction.y.signum());
+        }
+
+        // A star square to the travel direction shows up at cos θ' = β.
+
+        assert!((aberrate_direction(Vec2::Y, 0.9, Vec2::X).x - 0.9).abs() < 1e-6);
+    }
+}
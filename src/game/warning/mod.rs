@@ -0,0 +1,111 @@
+use bevy::prelude::*;
+use uom::si::{f64::Time as UomTime, time::day};
+
+use crate::shared::{SCREEN_HEIGHT_PX, SCREEN_WIDTH_PX};
+
+use super::{
+    object::Planet,
+    player::shared::Player,
+    preview::TrajectoryPreview,
+    shared::{
+        constants::COLLISION_WARNING_DAYS,
+        helpers::{danger_intensity, frames_to_collision, get_translation_from_position, length_to_pixel},
+        types::{CollisionResponse, Position, Radius, TimeCompression},
+    },
+};
+
+const VIGNETTE_LAYERS: usize = 6;
+const VIGNETTE_LAYER_PX: f32 = 4.0;
+const FLASH_HZ: f32 = 4.0;
+const DANGER_GLOW_HZ: f32 = 2.0;
+
+// Systems.
+
+pub fn collision_warning_update(
+    mut gizmos: Gizmos,
+    time: Res<Time>,
+    time_compression: Res<TimeCompression>,
+    trajectory_preview: Res<TrajectoryPreview>,
+    camera_query: Query<(&Transform, &OrthographicProjection), With<Camera>>,
+    player_query: Query<&Radius, (With<Player>, With<Position>)>,
+    planet_query: Query<(&Position, &Radius, &CollisionResponse), With<Planet>>,
+) {
+    let Ok((camera_transform, projection)) = camera_query.get_single() else {
+        return;
+    };
+
+    let Ok(player_radius) = player_query.get_single() else {
+        return;
+    };
+
+    // Look a fixed span of simulated time ahead, however the time compression sizes each preview step.
+
+    let path = trajectory_preview.remaining_path(&time_compression);
+    let steps = (UomTime::new::<day>(COLLISION_WARNING_DAYS) / trajectory_preview.time_step)
+        .value
+        .ceil() as usize;
+
+    // Only warn about planets that would end the run; bouncing off or passing through one is safe.
+
+    let planets: Vec<_> = planet_query
+        .iter()
+        .filter(|(.., collision_response)| **collision_response == CollisionResponse::Fail)
+        .map(|(position, radius, _)| (*position, *radius))
+        .collect();
+    let frames = frames_to_collision(&path[..path.len().min(steps.saturating_add(1))], &planets, player_radius);
+
+    if frames.is_none() {
+        return;
+    }
+
+    // Flash a red vignette around the edge of the view.
+
+    let flash = 0.5 + 0.5 * (time.elapsed_seconds() * FLASH_HZ * std::f32::consts::TAU).sin();
+    let zoom = projection.scale;
+    let center = camera_transform.translation.truncate();
+    let view = Vec2::new(SCREEN_WIDTH_PX as f32, SCREEN_HEIGHT_PX as f32) * zoom;
+
+    for layer in 0..VIGNETTE_LAYERS {
+        let inset = 2.0 * layer as f32 * VIGNETTE_LAYER_PX * zoom;
+        let alpha = flash * (1.0 - layer as f32 / VIGNETTE_LAYERS as f32);
+
+        gizmos.rect_2d(center, 0.0, view - Vec2::splat(inset), Color::rgba(1.0, 0.0, 0.0, alpha));
+    }
+}
+
+pub fn planet_danger_glow_update(
+    mut gizmos: Gizmos,
+    time: Res<Time>,
+    player_query: Query<&Position, With<Player>>,
+    planet_query: Query<(&Position, &Radius), With<Planet>>,
+) {
+    let Ok(player_position) = player_query.get_single() else {
+        return;
+    };
+
+    let pulse = 0.75 + 0.25 * (time.elapsed_seconds() * DANGER_GLOW_HZ * std::f32::consts::TAU).sin();
+
+    for (planet_position, planet_radius) in planet_query.iter() {
+        let delta_x = player_position.x - planet_position.x;
+        let delta_y = player_position.y - planet_position.y;
+        let intensity = danger_intensity((delta_x * delta_x + delta_y * delta_y).sqrt(), planet_radius.value);
+
+        if intensity <= 0.0 {
+            continue;
+        }
+
+        // Ring the surface, thickening and brightening as the player closes in.
+
+        let center = get_translation_from_position(planet_position).truncate();
+        let radius_px = length_to_pixel(planet_radius.value) as f32;
+        let alpha = pulse * intensity;
+
+        for ring in 0..3 {
+            gizmos.circle_2d(
+                center,
+                radius_px + 2.0 * ring as f32 * intensity,
+                Color::rgba(1.0, 0.0, 0.0, alpha / (ring + 1) as f32),
+            );
+        }
+    }
+}
@@ -0,0 +1,4 @@
+pub mod game;
+pub mod menu;
+pub mod shared;
+pub mod sim;
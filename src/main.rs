@@ -1,20 +1,36 @@
-pub mod game;
-pub mod menu;
-pub mod shared;
-
 use bevy::prelude::*;
-use game::{levels::CurrentLevel, GamePlugin};
-use menu::MenuPlugin;
-use shared::{state::AppState, types::spawn_camera};
+use relativity::{
+    game::{
+        levels::{CurrentLevel, LevelList},
+        player::player_sprite::ScriptedLaunch,
+        GamePlugin,
+    },
+    menu::MenuPlugin,
+    shared::{
+        state::AppState,
+        types::{spawn_camera, theme_update, Theme},
+    },
+};
 
 fn main() {
-    App::new()
-        .insert_resource(ClearColor(Color::rgb(0.0, 0.0, 0.0)))
+    let mut app = App::new();
+
+    app.insert_resource(ClearColor(Theme::default().background))
+        .init_resource::<Theme>()
         .init_resource::<CurrentLevel>()
         .add_plugins(DefaultPlugins)
+        .init_resource::<LevelList>()
         .add_plugins(MenuPlugin)
         .add_plugins(GamePlugin)
         .add_state::<AppState>()
         .add_systems(Startup, spawn_camera)
-        .run();
+        .add_systems(Update, theme_update);
+
+    // Launch hands-free, e.g., for demos and benchmarks.
+
+    if let Some(scripted_launch) = ScriptedLaunch::from_env() {
+        app.insert_resource(scripted_launch);
+    }
+
+    app.run();
 }
@@ -1,21 +1,256 @@
-use crate::shared::state::AppState;
+use crate::{
+    game::{
+        levels::{CurrentLevel, LevelList},
+        player::player_sprite::{ShipSkin, SHIP_SKINS},
+    },
+    shared::{
+        state::AppState,
+        types::{Theme, THEME_BACKGROUNDS},
+    },
+};
 use bevy::prelude::*;
 
 pub struct MenuPlugin;
 
 impl Plugin for MenuPlugin {
     fn build(&self, app: &mut App) {
-        app.add_systems(Update, start.run_if(in_state(AppState::Menu)));
+        app.add_systems(OnEnter(AppState::Menu), spawn_level_select)
+            .add_systems(OnExit(AppState::Menu), despawn_level_select)
+            .add_systems(
+                Update,
+                (
+                    level_select_interaction,
+                    selector_interaction::<SkinButton>,
+                    selector_interaction::<ThemeButton>,
+                )
+                    .run_if(in_state(AppState::Menu)),
+            );
     }
 }
 
-pub fn start(mut mouse_input: ResMut<Input<MouseButton>>, mut state: ResMut<NextState<AppState>>) {
-    if !mouse_input.pressed(MouseButton::Left) {
-        return;
+// Components.
+
+#[derive(Component, Default)]
+pub struct LevelSelectItem;
+
+#[derive(Component)]
+pub struct LevelButton {
+    pub level: CurrentLevel,
+}
+
+#[derive(Component)]
+pub struct SkinButton {
+    pub sprite: &'static str,
+}
+
+#[derive(Component)]
+pub struct ThemeButton {
+    pub background: Color,
+}
+
+/// A button in a row of options, where the pressed one picks the setting and stays highlighted.
+pub trait SelectorButton: Component {
+    type Setting: Resource;
+
+    fn is_selected(&self, setting: &Self::Setting) -> bool;
+    fn select(&self, setting: &mut Self::Setting);
+}
+
+impl SelectorButton for SkinButton {
+    type Setting = ShipSkin;
+
+    fn is_selected(&self, setting: &ShipSkin) -> bool {
+        setting.sprite == self.sprite
+    }
+
+    fn select(&self, setting: &mut ShipSkin) {
+        setting.sprite = self.sprite.to_string();
+    }
+}
+
+impl SelectorButton for ThemeButton {
+    type Setting = Theme;
+
+    fn is_selected(&self, setting: &Theme) -> bool {
+        setting.background == self.background
+    }
+
+    fn select(&self, setting: &mut Theme) {
+        setting.background = self.background;
+    }
+}
+
+// Startup systems.
+
+pub fn spawn_level_select(mut commands: Commands, asset_server: Res<AssetServer>, level_list: Res<LevelList>) {
+    let text_style = TextStyle {
+        font_size: 40.0,
+        font: asset_server.load("fonts/HackNerdFontMono-Regular.ttf"),
+        ..Default::default()
+    };
+
+    let root = NodeBundle {
+        style: Style {
+            width: Val::Percent(100.0),
+            height: Val::Percent(100.0),
+            flex_direction: FlexDirection::Column,
+            align_items: AlignItems::Center,
+            justify_content: JustifyContent::Center,
+            row_gap: Val::Px(10.0),
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+
+    commands.spawn((LevelSelectItem, root)).with_children(|parent| {
+        parent.spawn(TextBundle::from_section("Select a level", text_style.clone()));
+
+        for level in level_list.levels.iter() {
+            let button = ButtonBundle {
+                style: Style {
+                    padding: UiRect::all(Val::Px(10.0)),
+                    ..Default::default()
+                },
+                background_color: Color::rgb(0.15, 0.15, 0.15).into(),
+                ..Default::default()
+            };
+
+            parent
+                .spawn((LevelButton { level: level.clone() }, button))
+                .with_children(|button| {
+                    button.spawn(TextBundle::from_section(level.label(), text_style.clone()));
+                });
+        }
+
+        // Ship skins sit in a row beneath the levels.
+
+        parent.spawn(TextBundle::from_section("Select a ship", text_style.clone()));
+
+        let row = NodeBundle {
+            style: Style {
+                flex_direction: FlexDirection::Row,
+                column_gap: Val::Px(10.0),
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+
+        parent.spawn(row.clone()).with_children(|row| {
+            for sprite in SHIP_SKINS {
+                let button = ButtonBundle {
+                    style: Style {
+                        padding: UiRect::all(Val::Px(10.0)),
+                        ..Default::default()
+                    },
+                    background_color: Color::rgb(0.15, 0.15, 0.15).into(),
+                    ..Default::default()
+                };
+
+                row.spawn((SkinButton { sprite }, button)).with_children(|button| {
+                    button.spawn(ImageBundle {
+                        style: Style {
+                            width: Val::Px(40.0),
+                            height: Val::Px(60.0),
+                            ..Default::default()
+                        },
+                        image: asset_server.load(sprite).into(),
+                        ..Default::default()
+                    });
+                });
+            }
+        });
+
+        // Background themes sit in a row beneath the skins, each shown as a swatch of its color.
+
+        parent.spawn(TextBundle::from_section("Select a background", text_style.clone()));
+
+        parent.spawn(row).with_children(|row| {
+            for background in THEME_BACKGROUNDS {
+                let button = ButtonBundle {
+                    style: Style {
+                        padding: UiRect::all(Val::Px(10.0)),
+                        ..Default::default()
+                    },
+                    background_color: Color::rgb(0.15, 0.15, 0.15).into(),
+                    ..Default::default()
+                };
+
+                row.spawn((ThemeButton { background }, button)).with_children(|button| {
+                    button.spawn(NodeBundle {
+                        style: Style {
+                            width: Val::Px(40.0),
+                            height: Val::Px(40.0),
+                            ..Default::default()
+                        },
+                        background_color: background.into(),
+                        ..Default::default()
+                    });
+                });
+            }
+        });
+    });
+}
+
+pub fn despawn_level_select(mut commands: Commands, query: Query<Entity, With<LevelSelectItem>>) {
+    for entity in query.iter() {
+        commands.entity(entity).despawn_recursive();
     }
+}
+
+// Systems.
+
+pub fn level_select_interaction(
+    mut mouse_input: ResMut<Input<MouseButton>>,
+    mut button_query: Query<(&Interaction, &LevelButton, &mut BackgroundColor), Changed<Interaction>>,
+    mut current_level: ResMut<CurrentLevel>,
+    mut state: ResMut<NextState<AppState>>,
+) {
+    for (interaction, button, mut background_color) in button_query.iter_mut() {
+        match interaction {
+            Interaction::Pressed => {
+                // Swallow the click, so it doesn't also launch the player.
+
+                mouse_input.release_all();
+                mouse_input.reset_all();
+
+                *current_level = button.level.clone();
+                state.set(AppState::InGame);
+            }
+            Interaction::Hovered => *background_color = Color::rgb(0.3, 0.3, 0.3).into(),
+            Interaction::None => *background_color = Color::rgb(0.15, 0.15, 0.15).into(),
+        }
+    }
+}
 
-    mouse_input.release_all();
-    mouse_input.reset_all();
+pub fn selector_interaction<B: SelectorButton>(
+    mut mouse_input: ResMut<Input<MouseButton>>,
+    mut button_query: Query<(&Interaction, &B, &mut BackgroundColor)>,
+    mut setting: ResMut<B::Setting>,
+) {
+    for (interaction, button, _) in button_query.iter() {
+        if *interaction != Interaction::Pressed {
+            continue;
+        }
 
-    state.set(AppState::InGame);
+        // Swallow the click, so it doesn't also reach whatever is under the menu.
+
+        mouse_input.release_all();
+        mouse_input.reset_all();
+
+        if !button.is_selected(&setting) {
+            button.select(&mut setting);
+        }
+    }
+
+    // Keep the chosen option highlighted, whatever the hover state.
+
+    for (interaction, button, mut background_color) in button_query.iter_mut() {
+        *background_color = if button.is_selected(&setting) {
+            Color::rgb(0.4, 0.4, 0.1).into()
+        } else if *interaction == Interaction::Hovered {
+            Color::rgb(0.3, 0.3, 0.3).into()
+        } else {
+            Color::rgb(0.15, 0.15, 0.15).into()
+        };
+    }
 }
@@ -12,5 +12,6 @@ pub enum GameState {
     #[default]
     Paused,
     Running,
+    SimPaused,
     Finished,
 }
@@ -1,4 +1,5 @@
 use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
 
 use super::{SCREEN_HEIGHT_PX, SCREEN_WIDTH_PX};
 
@@ -9,3 +10,52 @@ pub fn spawn_camera(mut commands: Commands) {
 
     commands.spawn(Camera2dBundle { transform, ..Default::default() });
 }
+
+// Theme.
+
+/// The background colors the player can pick from in the menu.
+pub const THEME_BACKGROUNDS: [Color; 4] = [
+    Color::rgb(0.0, 0.0, 0.0),
+    Color::rgb(0.02, 0.02, 0.08),
+    Color::rgb(0.06, 0.02, 0.08),
+    Color::rgb(0.02, 0.06, 0.06),
+];
+
+#[derive(Resource, Serialize, Deserialize, Clone, Copy, Debug, PartialEq)]
+pub struct Theme {
+    pub background: Color,
+}
+
+impl Default for Theme {
+    fn default() -> Self {
+        Self { background: THEME_BACKGROUNDS[0] }
+    }
+}
+
+pub fn theme_update(theme: Res<Theme>, mut clear_color: ResMut<ClearColor>) {
+    if !theme.is_changed() {
+        return;
+    }
+
+    clear_color.0 = theme.background;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn changing_the_theme_background_updates_the_clear_color() {
+        let mut app = App::new();
+
+        app.insert_resource(ClearColor(Theme::default().background))
+            .init_resource::<Theme>()
+            .add_systems(Update, theme_update);
+        app.update();
+
+        app.world.resource_mut::<Theme>().background = THEME_BACKGROUNDS[2];
+        app.update();
+
+        assert_eq!(app.world.resource::<ClearColor>().0, THEME_BACKGROUNDS[2]);
+    }
+}
@@ -0,0 +1,957 @@
+use bevy::{ecs::event::ManualEventReader, prelude::*};
+use uom::si::f64::Time as UomTime;
+
+use crate::{
+    game::{
+        destination::WaypointProgress,
+        levels::{spawn_level, CurrentLevel},
+        observer::Observer,
+        physics_systems,
+        player::{
+            player_sprite::{launch_velocity, LaunchConfig, ShipSkin},
+            shared::Player,
+        },
+        shared::{
+            constants::PHYSICS_TIMESTEP_SECONDS,
+            systems::launch_origin_record,
+            types::{
+                Clock, CollisionEvent, GameplayScale, GravFrame, LaunchOrigin, PhysicsConfig, Position, RunFailed, TimeCompression,
+                Velocity, WorldBounds,
+            },
+        },
+    },
+    shared::state::{AppState, GameState},
+};
+
+// Types.
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct SimSummary {
+    pub position: Position,
+    pub velocity: Velocity,
+    pub observer_time: UomTime,
+    pub player_time: UomTime,
+    /// `Finished` on success, `Paused` on failure, and `Running` if still in flight.
+    pub outcome: GameState,
+}
+
+// Entry points.
+
+/// An app that runs the game's physics without rendering, with nothing spawned and `GameState::Paused`; every update
+/// is exactly one physics step of `PHYSICS_TIMESTEP_SECONDS`, so runs are deterministic regardless of wall-clock time.
+pub fn headless_app() -> App {
+    let mut app = headless_app_without_physics();
+
+    app.add_systems(Update, physics_systems().run_if(in_state(GameState::Running)));
+
+    app
+}
+
+/// Spawns `level` without rendering, launches the player at `launch` (angle in radians, power in `[0, 1]`), and runs
+/// `frames` physics steps of `PHYSICS_TIMESTEP_SECONDS` each, stopping early if the run fails. Returns `None` if the
+/// level doesn't spawn exactly one player and one observer.
+pub fn run_headless(level: CurrentLevel, launch: (f64, f64), frames: usize) -> Option<SimSummary> {
+    let mut app = headless_app();
+
+    app.insert_resource(level).add_systems(Startup, spawn_level);
+
+    // Spawn the level, then launch.
+
+    app.update();
+
+    let (angle, power) = launch;
+    let launch_config = app.world.get_resource::<LaunchConfig>().cloned().unwrap_or_default();
+
+    let mut player_velocity = app
+        .world
+        .query_filtered::<&mut Velocity, With<Player>>()
+        .get_single_mut(&mut app.world)
+        .ok()?;
+
+    *player_velocity = launch_velocity(angle, power, &launch_config);
+    app.world.resource_mut::<NextState<GameState>>().set(GameState::Running);
+
+    // Failures only send an event, which the game turns into a pause; do the same here.
+
+    let mut run_failed_reader = ManualEventReader::<RunFailed>::default();
+
+    for _ in 0..frames {
+        app.update();
+
+        if run_failed_reader.iter(app.world.resource::<Events<RunFailed>>()).next().is_some() {
+            app.world.resource_mut::<NextState<GameState>>().set(GameState::Paused);
+            app.update();
+            break;
+        }
+    }
+
+    let (position, velocity) = app
+        .world
+        .query_filtered::<(&Position, &Velocity), With<Player>>()
+        .get_single(&app.world)
+        .ok()?;
+    let (position, velocity) = (*position, *velocity);
+
+    let observer_time = app
+        .world
+        .query_filtered::<&Clock, With<Observer>>()
+        .get_single(&app.world)
+        .ok()?
+        .value;
+    let player_time = app
+        .world
+        .query_filtered::<&Clock, (With<Player>, Without<Observer>)>()
+        .get_single(&app.world)
+        .ok()?
+        .value;
+
+    Some(SimSummary {
+        position,
+        velocity,
+        observer_time,
+        player_time,
+        outcome: app.world.resource::<State<GameState>>().get().clone(),
+    })
+}
+
+// Helpers.
+
+/// Everything `headless_app` has but the physics systems themselves, so tests can schedule them as the game does.
+fn headless_app_without_physics() -> App {
+    let mut app = App::new();
+
+    app.add_plugins((MinimalPlugins, AssetPlugin::default()))
+        .add_state::<AppState>()
+        .add_state::<GameState>()
+        .insert_resource(FixedTime::new_from_secs(PHYSICS_TIMESTEP_SECONDS))
+        .init_resource::<TimeCompression>()
+        .init_resource::<PhysicsConfig>()
+        .init_resource::<GameplayScale>()
+        .init_resource::<GravFrame>()
+        .init_resource::<WorldBounds>()
+        .init_resource::<LaunchOrigin>()
+        .init_resource::<WaypointProgress>()
+        .init_resource::<ShipSkin>()
+        .add_event::<CollisionEvent>()
+        .add_event::<RunFailed>()
+        .add_systems(
+            OnTransition {
+                from: GameState::Paused,
+                to: GameState::Running,
+            },
+            launch_origin_record,
+        );
+
+    app
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game::{
+        assist::{gravity_assist_check, AssistCounter, GravityAssistTracker},
+        black_hole::BlackHoleBundle,
+        destination::Destination,
+        object::Planet,
+        shared::{
+            constants::{C, G, MASS_OF_SUN, MAX_VELOCITY_FRACTION_OF_C},
+            helpers::{circular_orbit_velocity_vector, gravitational_acceleration, schwarzschild_radius, total_energy, verlet_step},
+            systems::integrate_motion,
+            types::{
+                Atmosphere, BoundaryMode, CollisionKind, CollisionResponse, GravitationalGamma, GravityAffected, GravitySource,
+                InfluenceRadius, Mass, Radius, VelocityGamma,
+            },
+        },
+    };
+    use bevy::{ecs::query::ReadOnlyWorldQuery, time::TimeUpdateStrategy};
+    use glam::DVec2;
+    use uom::si::{
+        f64::{Length as UomLength, Velocity as UomVelocity},
+        length::meter,
+        velocity::meter_per_second,
+    };
+
+    fn at(x: f64, y: f64) -> Position {
+        Position {
+            x: UomLength::new::<meter>(x),
+            y: UomLength::new::<meter>(y),
+        }
+    }
+
+    fn separation(a: &Position, b: &Position) -> f64 {
+        DVec2::new((a.x - b.x).value, (a.y - b.y).value).length()
+    }
+
+    /// A running app with a bare player at `position` moving at `velocity`, plus the clocks the physics systems expect;
+    /// each test spawns its own masses.
+    fn scene_app(position: Position, velocity: Velocity) -> App {
+        let mut app = headless_app();
+
+        app.world.spawn((
+            Player,
+            GravityAffected,
+            position,
+            velocity,
+            Radius { value: UomLength::new::<meter>(1.0) },
+        ));
+        app.world
+            .spawn((Player, Clock::default(), VelocityGamma::default(), GravitationalGamma::default()));
+        app.world.spawn((Observer, Clock::default()));
+        app.world.resource_mut::<NextState<GameState>>().set(GameState::Running);
+
+        app
+    }
+
+    fn count<F: ReadOnlyWorldQuery>(app: &mut App) -> usize {
+        app.world.query_filtered::<(), F>().iter(&app.world).count()
+    }
+
+    fn spawn_mass(app: &mut App, position: Position, mass: Mass) -> Entity {
+        app.world.spawn((GravitySource, position, mass)).id()
+    }
+
+    fn player_motion(app: &mut App) -> (Position, Velocity) {
+        let (position, velocity) = app
+            .world
+            .query_filtered::<(&Position, &Velocity), With<Player>>()
+            .single(&app.world);
+
+        (*position, *velocity)
+    }
+
+    /// The gravitational acceleration on level one's player as spawned, in m/s^2.
+    fn level_one_acceleration(gameplay_scale: GameplayScale) -> DVec2 {
+        let mut app = headless_app();
+
+        app.insert_resource(CurrentLevel::One)
+            .insert_resource(gameplay_scale)
+            .add_systems(Startup, spawn_level);
+        app.update();
+
+        let (player_entity, player_position) = app.world.query_filtered::<(Entity, &Position), With<Player>>().single(&app.world);
+        let (player_entity, player_position) = (player_entity, *player_position);
+
+        let masses: Vec<_> = app
+            .world
+            .query_filtered::<(Entity, &Position, &Mass, Option<&InfluenceRadius>), With<GravitySource>>()
+            .iter(&app.world)
+            .map(|(entity, position, mass, influence_radius)| (entity, *position, *mass, influence_radius.copied()))
+            .collect();
+
+        let (acceleration_x, acceleration_y) =
+            gravitational_acceleration(player_entity, &player_position, &masses, PhysicsConfig::default().softening);
+
+        DVec2::new(acceleration_x.value, acceleration_y.value)
+    }
+
+    #[test]
+    fn run_headless_is_deterministic() {
+        let first = run_headless(CurrentLevel::One, (0.8, 0.5), 120).expect("level one has a player and an observer");
+        let second = run_headless(CurrentLevel::One, (0.8, 0.5), 120).expect("level one has a player and an observer");
+
+        assert_eq!(first, second);
+    }
+
+    #[test]
+    fn run_headless_dilates_the_player_clock() {
+        let summary = run_headless(CurrentLevel::One, (0.8, 0.5), 120).expect("level one has a player and an observer");
+
+        assert!(summary.observer_time.value > 0.0);
+        assert!(summary.player_time < summary.observer_time);
+    }
+
+    #[test]
+    fn gameplay_scale_scales_the_pull_on_the_player() {
+        // Real masses keep every horizon tiny next to the level, so the relativistic adjustment stays within a hair of 1
+        // and the pull is proportional to the mass factor.
+
+        let light = level_one_acceleration(GameplayScale { mass_factor: 1.0 });
+        let heavy = level_one_acceleration(GameplayScale { mass_factor: 3.0 });
+
+        assert!(light.length() > 0.0);
+        assert!((heavy - 3.0 * light).length() < 1e-6 * heavy.length());
+    }
+
+    #[test]
+    fn speed_stays_below_c_through_a_deep_well() {
+        // Straight through a hundred million suns, which would push the player well past c without the clamp.
+
+        let mut app = scene_app(at(1e12, 0.0), Velocity { x: -0.5 * *C, ..Default::default() });
+        spawn_mass(&mut app, Position::default(), Mass { value: 1e8 * *MASS_OF_SUN });
+
+        let max_speed = MAX_VELOCITY_FRACTION_OF_C * *C;
+        let mut fastest = 0.0;
+
+        for frame in 0..300 {
+            app.update();
+
+            let (_, velocity) = player_motion(&mut app);
+            let speed = velocity.scalar();
+
+            // Allow for rounding in the rescale.
+
+            assert!(
+                speed.value <= max_speed.value * (1.0 + 1e-12),
+                "frame {}: {} m/s is over the cap",
+                frame,
+                speed.value
+            );
+
+            fastest = speed.value.max(fastest);
+        }
+
+        assert!(fastest > 0.99 * max_speed.value, "the well never got the player near the cap");
+    }
+
+    #[test]
+    fn axis_aligned_launch_curves_toward_a_mass() {
+        let mut app = scene_app(
+            Position::default(),
+            Velocity {
+                x: UomVelocity::new::<meter_per_second>(1e5),
+                ..Default::default()
+            },
+        );
+        spawn_mass(&mut app, at(0.0, 1e10), Mass { value: *MASS_OF_SUN });
+
+        for _ in 0..10 {
+            app.update();
+        }
+
+        let (position, velocity) = player_motion(&mut app);
+
+        assert!(velocity.y.value > 0.0);
+        assert!(position.y.value > 0.0);
+    }
+
+    #[test]
+    fn circular_orbit_keeps_its_radius() {
+        // About two turns around the sun; forward Euler drifts out by over 10% here.
+
+        let radius = 1.2e9;
+        let start = at(radius, 0.0);
+        let velocity = circular_orbit_velocity_vector(&Position::default(), *MASS_OF_SUN, &start);
+
+        let mut app = scene_app(start, velocity);
+        spawn_mass(&mut app, Position::default(), Mass { value: *MASS_OF_SUN });
+
+        for frame in 0..600 {
+            app.update();
+
+            let (position, _) = player_motion(&mut app);
+            let drift = (separation(&position, &Position::default()) - radius).abs() / radius;
+
+            assert!(drift < 0.05, "frame {}: the radius drifted by {:.1}%", frame, 100.0 * drift);
+        }
+    }
+
+    #[test]
+    fn two_equal_masses_stay_bound() {
+        // Two suns, launched at 80% of the circular speed about their barycenter, so they swing in and back out. They
+        // start wide enough apart that a frame is a small slice of the orbit, since each pull is taken from the
+        // frame's starting positions.
+
+        let half_separation = 3e9;
+        let circular_speed = (*G * *MASS_OF_SUN / UomLength::new::<meter>(4.0 * half_separation)).value.sqrt();
+        let speed = UomVelocity::new::<meter_per_second>(0.8 * circular_speed);
+
+        let mut app = scene_app(at(1e12, 1e12), Velocity::default());
+        let bodies = [
+            (at(-half_separation, 0.0), Velocity { y: -speed, ..Default::default() }),
+            (at(half_separation, 0.0), Velocity { y: speed, ..Default::default() }),
+        ]
+        .map(|(position, velocity)| {
+            app.world
+                .spawn((GravitySource, GravityAffected, position, velocity, Mass { value: *MASS_OF_SUN }))
+                .id()
+        });
+
+        let initial = 2.0 * half_separation;
+        let mut separations = Vec::new();
+
+        for _ in 0..2400 {
+            app.update();
+
+            let [a, b] = bodies.map(|body| *app.world.get::<Position>(body).unwrap());
+            separations.push(separation(&a, &b));
+        }
+
+        // Bound: never much past the starting (widest) separation. Oscillating: closes in and widens back out at least
+        // twice.
+
+        let widest = separations.iter().copied().fold(0.0, f64::max);
+        let turns = separations
+            .windows(3)
+            .filter(|window| (window[1] - window[0]) * (window[2] - window[1]) < 0.0)
+            .count();
+
+        assert!(widest < 1.05 * initial, "the bodies drifted apart to {} m", widest);
+        assert!(separations.iter().copied().fold(f64::MAX, f64::min) < 0.9 * initial);
+        assert!(turns >= 2, "the separation turned around {} times", turns);
+    }
+
+    #[test]
+    fn parallel_integration_matches_a_serial_reference() {
+        let mut app = scene_app(at(1e13, 1e13), Velocity::default());
+
+        // Fifty suns scattered on a spiral, each pulling on (and pulled by) the rest.
+
+        let bodies: Vec<_> = (0..50)
+            .map(|index| {
+                let angle = index as f64 * 0.7;
+                let distance = 1e10 * (1.0 + index as f64);
+                let position = at(distance * angle.cos(), distance * angle.sin());
+                let velocity = Velocity {
+                    x: UomVelocity::new::<meter_per_second>(-1e4 * angle.sin()),
+                    y: UomVelocity::new::<meter_per_second>(1e4 * angle.cos()),
+                };
+
+                let entity = app
+                    .world
+                    .spawn((GravitySource, GravityAffected, position, velocity, Mass { value: *MASS_OF_SUN }))
+                    .id();
+
+                (entity, position, velocity)
+            })
+            .collect();
+
+        // Step each body serially against the same snapshot of the masses.
+
+        let masses: Vec<_> = bodies
+            .iter()
+            .map(|(entity, position, _)| (*entity, *position, Mass { value: *MASS_OF_SUN }, None))
+            .collect();
+        let physics_config = PhysicsConfig::default();
+        let time_elapsed = TimeCompression::default().time_elapsed(app.world.resource::<FixedTime>().period.as_secs_f64());
+        let substeps = physics_config.substeps(time_elapsed);
+
+        let expected: Vec<_> = bodies
+            .iter()
+            .map(|(entity, position, velocity)| {
+                let (mut position, mut velocity) = (*position, *velocity);
+
+                for _ in 0..substeps {
+                    verlet_step(
+                        *entity,
+                        &mut position,
+                        &mut velocity,
+                        &masses,
+                        time_elapsed / substeps as f64,
+                        physics_config.softening,
+                    );
+                }
+
+                (position, velocity)
+            })
+            .collect();
+
+        app.update();
+
+        for ((entity, ..), (expected_position, expected_velocity)) in bodies.iter().zip(expected) {
+            let position = *app.world.get::<Position>(*entity).unwrap();
+            let velocity = *app.world.get::<Velocity>(*entity).unwrap();
+
+            assert!(separation(&position, &expected_position) <= 1e-9 * separation(&expected_position, &Position::default()));
+            assert!((velocity.x - expected_velocity.x).abs().value <= 1e-9 * expected_velocity.scalar().value);
+            assert!((velocity.y - expected_velocity.y).abs().value <= 1e-9 * expected_velocity.scalar().value);
+        }
+    }
+
+    /// Runs one step of a player flying out past the right edge under `mode`, returning where it started.
+    fn cross_the_right_edge(mode: BoundaryMode) -> (App, Position) {
+        let world_bounds = WorldBounds { mode, ..Default::default() };
+        let start = Position {
+            x: world_bounds.width - UomLength::new::<meter>(1e9),
+            y: world_bounds.height / 2.0,
+        };
+
+        let mut app = scene_app(start, Velocity { x: 0.5 * *C, ..Default::default() });
+        app.insert_resource(world_bounds);
+        app.update();
+
+        (app, start)
+    }
+
+    #[test]
+    fn boundary_none_lets_the_player_leave() {
+        let (mut app, _) = cross_the_right_edge(BoundaryMode::None);
+        let (position, velocity) = player_motion(&mut app);
+
+        assert!(position.x > WorldBounds::default().width);
+        assert_eq!(velocity.x, 0.5 * *C);
+    }
+
+    #[test]
+    fn boundary_clamp_stops_at_the_edge() {
+        let (mut app, start) = cross_the_right_edge(BoundaryMode::Clamp);
+        let (position, velocity) = player_motion(&mut app);
+
+        assert_eq!(position.x, WorldBounds::default().width);
+        assert_eq!(position.y, start.y);
+        assert_eq!(velocity.x.value, 0.0);
+    }
+
+    #[test]
+    fn boundary_wrap_teleports_across() {
+        let (mut app, start) = cross_the_right_edge(BoundaryMode::Wrap);
+        let (position, velocity) = player_motion(&mut app);
+
+        assert!(position.x.value > 0.0 && position.x < start.x - UomLength::new::<meter>(1e12));
+        assert_eq!(position.y, start.y);
+        assert_eq!(velocity.x, 0.5 * *C);
+    }
+
+    #[test]
+    fn boundary_fail_on_exit_resets_and_fails() {
+        let (mut app, start) = cross_the_right_edge(BoundaryMode::FailOnExit);
+        let (position, velocity) = player_motion(&mut app);
+
+        assert_eq!(app.world.resource::<LaunchOrigin>().0, start);
+        assert_eq!(position, start);
+        assert_eq!(velocity, Velocity::default());
+
+        let mut run_failed_reader = ManualEventReader::<RunFailed>::default();
+        let reasons: Vec<_> = run_failed_reader
+            .iter(app.world.resource::<Events<RunFailed>>())
+            .map(|run_failed| run_failed.reason)
+            .collect();
+
+        assert_eq!(reasons, ["out of bounds"]);
+    }
+
+    /// A player at rest beside three ordered waypoints and the final destination, all well out of reach, so only the
+    /// collisions each test sends count.
+    fn waypoint_app() -> (App, [Entity; 3], Entity) {
+        let mut app = scene_app(Position::default(), Velocity::default());
+        let mut spawn_destination = |destination: Destination, index: f64| {
+            app.world
+                .spawn((destination, at(1e12, 1e12 * index), Radius { value: UomLength::new::<meter>(1.0) }))
+                .id()
+        };
+
+        let waypoints = [0, 1, 2].map(|order| spawn_destination(Destination::Waypoint { order }, order as f64));
+        let last = spawn_destination(Destination::Final, 3.0);
+
+        app.update();
+
+        (app, waypoints, last)
+    }
+
+    /// Sends the player touching `destination`, and returns the state once it has been handled.
+    fn touch(app: &mut App, destination: Entity) -> GameState {
+        let player = app
+            .world
+            .query_filtered::<Entity, (With<Player>, With<Position>)>()
+            .single(&app.world);
+
+        app.world.send_event(CollisionEvent {
+            player,
+            other: destination,
+            kind: CollisionKind::Destination,
+        });
+
+        // Handle the event, then apply any state change.
+
+        app.update();
+        app.update();
+
+        app.world.resource::<State<GameState>>().get().clone()
+    }
+
+    #[test]
+    fn waypoints_in_order_finish_at_the_final_destination() {
+        let (mut app, [first, second, third], last) = waypoint_app();
+
+        assert_eq!(touch(&mut app, first), GameState::Running);
+        assert_eq!(touch(&mut app, second), GameState::Running);
+
+        // The final destination doesn't count until every waypoint is visited.
+
+        assert_eq!(touch(&mut app, last), GameState::Running);
+        assert_eq!(touch(&mut app, third), GameState::Running);
+        assert_eq!(touch(&mut app, last), GameState::Finished);
+    }
+
+    #[test]
+    fn waypoints_out_of_order_do_nothing() {
+        let (mut app, [first, second, third], last) = waypoint_app();
+
+        assert_eq!(touch(&mut app, second), GameState::Running);
+        assert_eq!(touch(&mut app, third), GameState::Running);
+        assert_eq!(touch(&mut app, last), GameState::Running);
+        assert_eq!(app.world.resource::<WaypointProgress>().next, 0);
+
+        assert_eq!(touch(&mut app, first), GameState::Running);
+        assert_eq!(touch(&mut app, third), GameState::Running);
+        assert_eq!(touch(&mut app, last), GameState::Running);
+        assert_eq!(app.world.resource::<WaypointProgress>().next, 1);
+
+        assert_eq!(touch(&mut app, second), GameState::Running);
+        assert_eq!(touch(&mut app, third), GameState::Running);
+        assert_eq!(touch(&mut app, last), GameState::Finished);
+    }
+
+    #[test]
+    fn slingshot_level_spawns_one_planet_and_one_destination() {
+        let mut app = headless_app();
+
+        app.insert_resource(CurrentLevel::Slingshot).add_systems(Startup, spawn_level);
+        app.update();
+
+        assert_eq!(count::<(With<Player>, With<Position>)>(&mut app), 1);
+        assert_eq!(count::<(With<Player>, With<Clock>)>(&mut app), 1);
+        assert_eq!(count::<(With<Observer>, With<Clock>)>(&mut app), 1);
+        assert_eq!(count::<With<Planet>>(&mut app), 1);
+        assert_eq!(count::<With<Destination>>(&mut app), 1);
+    }
+
+    #[test]
+    fn slingshot_level_needs_a_gravity_assist() {
+        // Straight at the destination runs into the sun; swinging out and around it gets there.
+
+        let straight = run_headless(CurrentLevel::Slingshot, (0.0, 0.4), 600).expect("the slingshot level has a player and an observer");
+        let assisted = run_headless(CurrentLevel::Slingshot, (0.5, 0.4), 600).expect("the slingshot level has a player and an observer");
+
+        assert_eq!(straight.outcome, GameState::Paused);
+        assert_eq!(assisted.outcome, GameState::Finished);
+    }
+
+    #[test]
+    fn atmosphere_slows_the_player_only_inside_its_band() {
+        let launch = Velocity {
+            y: UomVelocity::new::<meter_per_second>(1e5),
+            ..Default::default()
+        };
+
+        let speed_after_ten_frames = |x: f64| {
+            let mut app = scene_app(at(x, 1e12), launch);
+            app.world.spawn((
+                at(1e12, 1e12),
+                Atmosphere {
+                    outer_radius: UomLength::new::<meter>(1e11),
+                    drag_coeff: 1.0,
+                },
+            ));
+
+            for _ in 0..10 {
+                app.update();
+            }
+
+            player_motion(&mut app).1.scalar()
+        };
+
+        // With no masses about, nothing else changes the speed.
+
+        let inside = speed_after_ten_frames(1.05e12);
+        let outside = speed_after_ten_frames(1.5e12);
+
+        assert!(inside < launch.scalar());
+        assert_eq!(outside, launch.scalar());
+    }
+
+    /// The kinds of collision sent in one step with the player at rest on top of whatever `spawn` adds.
+    fn collision_kinds(spawn: impl FnOnce(&mut World, Position)) -> Vec<CollisionKind> {
+        let position = at(1e12, 1e12);
+
+        let mut app = scene_app(position, Velocity::default());
+        spawn(&mut app.world, position);
+        app.update();
+
+        let mut collision_reader = ManualEventReader::<CollisionEvent>::default();
+
+        collision_reader
+            .iter(app.world.resource::<Events<CollisionEvent>>())
+            .map(|collision| collision.kind)
+            .collect()
+    }
+
+    #[test]
+    fn collisions_tell_destinations_from_planets() {
+        let radius = Radius { value: UomLength::new::<meter>(1e9) };
+
+        let destination = collision_kinds(|world, position| {
+            world.spawn((Destination::Final, position, radius));
+        });
+        let planet = collision_kinds(|world, position| {
+            world.spawn((Planet, position, radius, CollisionResponse::Fail));
+        });
+
+        assert_eq!(destination, [CollisionKind::Destination]);
+        assert_eq!(planet, [CollisionKind::Planet]);
+    }
+
+    /// Where the player ends up after `updates` frames of `steps_per_update` physics steps each, with the physics in
+    /// `FixedUpdate` as the game schedules it.
+    fn fixed_update_run(steps_per_update: u32, updates: usize) -> Position {
+        let mut app = headless_app_without_physics();
+
+        let period = app.world.resource::<FixedTime>().period;
+
+        app.insert_resource(TimeUpdateStrategy::ManualDuration(period * steps_per_update))
+            .add_systems(FixedUpdate, physics_systems().run_if(in_state(GameState::Running)));
+
+        // The first update runs a single fixed step whatever the frame length, so get it out of the way before there is
+        // anything to move.
+
+        app.update();
+
+        app.world.spawn((
+            Player,
+            GravityAffected,
+            at(1e12, 1e12),
+            Velocity {
+                x: UomVelocity::new::<meter_per_second>(1e6),
+                ..Default::default()
+            },
+            Radius { value: UomLength::new::<meter>(1.0) },
+        ));
+        app.world
+            .spawn((Player, Clock::default(), VelocityGamma::default(), GravitationalGamma::default()));
+        app.world.spawn((Observer, Clock::default()));
+        spawn_mass(&mut app, at(1.5e12, 1.5e12), Mass { value: *MASS_OF_SUN });
+        app.world.resource_mut::<NextState<GameState>>().set(GameState::Running);
+
+        for _ in 0..updates {
+            app.update();
+        }
+
+        player_motion(&mut app).0
+    }
+
+    #[test]
+    fn frame_rate_does_not_change_the_trajectory() {
+        // The same four seconds, at 120 and at 30 frames per second.
+
+        let fast = fixed_update_run(1, 480);
+        let slow = fixed_update_run(4, 120);
+
+        assert!(separation(&fast, &at(1e12, 1e12)) > 1e9, "the player never moved");
+        assert!(separation(&fast, &slow) <= 1e-9 * separation(&fast, &at(1e12, 1e12)));
+    }
+
+    /// Flies the player at a massless planet for one step at 64x time compression, far enough to cross it whole, and
+    /// returns where the player ends up, where the planet is, and the resulting state.
+    fn fly_through_a_planet(physics_config: PhysicsConfig) -> (Position, Position, GameState) {
+        let planet_position = at(1.002e12, 1e12);
+
+        let mut app = scene_app(
+            at(1e12, 1e12),
+            Velocity {
+                x: UomVelocity::new::<meter_per_second>(1e6),
+                ..Default::default()
+            },
+        );
+        app.insert_resource(TimeCompression(64.0 * TimeCompression::default().0))
+            .insert_resource(physics_config);
+        app.world.spawn((
+            Planet,
+            planet_position,
+            Radius { value: UomLength::new::<meter>(1e8) },
+            CollisionResponse::Fail,
+        ));
+
+        // Step, then apply any state change.
+
+        app.update();
+        app.update();
+
+        let (position, _) = player_motion(&mut app);
+
+        (position, planet_position, app.world.resource::<State<GameState>>().get().clone())
+    }
+
+    #[test]
+    fn substeps_keep_a_fast_player_from_tunneling() {
+        // A single step covers about 4.6e9 m, from 2e9 m short of the planet to well past it.
+
+        let (position, planet_position, state) = fly_through_a_planet(PhysicsConfig { max_step_days: 0.0, ..Default::default() });
+
+        assert!(position.x > planet_position.x + UomLength::new::<meter>(1e8));
+        assert_eq!(state, GameState::Running);
+
+        // Substeps of the default size stop at the surface, and the run fails there.
+
+        let (position, planet_position, state) = fly_through_a_planet(PhysicsConfig::default());
+
+        assert!(separation(&position, &planet_position) <= 1e8 + 1.0);
+        assert_eq!(state, GameState::Paused);
+    }
+
+    #[test]
+    fn player_atop_a_mass_stays_finite() {
+        // Barely moving, so the integrator runs, from the exact center of a mass at game scale.
+
+        let center = at(1e12, 1e12);
+
+        let mut app = scene_app(
+            center,
+            Velocity {
+                x: UomVelocity::new::<meter_per_second>(1.0),
+                ..Default::default()
+            },
+        );
+        spawn_mass(&mut app, center, GameplayScale::default().mass(*MASS_OF_SUN));
+
+        for frame in 0..10 {
+            app.update();
+
+            let (position, velocity) = player_motion(&mut app);
+            let (velocity_gamma, gravitational_gamma) = app
+                .world
+                .query_filtered::<(&VelocityGamma, &GravitationalGamma), With<Player>>()
+                .single(&app.world);
+
+            assert!(
+                position.x.is_finite() && position.y.is_finite(),
+                "frame {}: position is not finite",
+                frame
+            );
+            assert!(
+                velocity.x.is_finite() && velocity.y.is_finite(),
+                "frame {}: velocity is not finite",
+                frame
+            );
+            assert!(velocity_gamma.value.is_finite(), "frame {}: γ_v is not finite", frame);
+            assert!(gravitational_gamma.value.is_finite(), "frame {}: γ_g is not finite", frame);
+        }
+    }
+
+    #[test]
+    fn verlet_keeps_the_total_energy_within_bounds() {
+        // About one turn of an eccentric orbit around the sun; forward Euler drifts by about 25% here.
+
+        let start = at(1.2e9, 0.0);
+        let circular = circular_orbit_velocity_vector(&Position::default(), *MASS_OF_SUN, &start);
+        let velocity = Velocity { x: 0.9 * circular.x, y: 0.9 * circular.y };
+
+        let mut app = scene_app(start, velocity);
+        let sun = spawn_mass(&mut app, Position::default(), Mass { value: *MASS_OF_SUN });
+        let masses = [(sun, Position::default(), Mass { value: *MASS_OF_SUN }, None)];
+
+        let initial = total_energy(&velocity, &start, &masses);
+
+        for frame in 0..300 {
+            app.update();
+
+            let (position, velocity) = player_motion(&mut app);
+            let drift = ((total_energy(&velocity, &position, &masses) - initial) / initial).abs();
+
+            assert!(drift < 1e-3, "frame {}: the total energy drifted by {:.3}%", frame, 100.0 * drift);
+        }
+    }
+
+    #[test]
+    fn a_hyperbolic_pass_counts_one_assist() {
+        // A thousand suns, passed 2e9 m off-center, which bends the course by about 60 degrees; the planet is there to
+        // be passed, not hit.
+
+        let center = at(3e12, 1.5e12);
+
+        let mut app = scene_app(
+            at(3e12 - 3e10, 1.5e12 + 2e9),
+            Velocity {
+                x: UomVelocity::new::<meter_per_second>(1.07e7),
+                ..Default::default()
+            },
+        );
+        app.init_resource::<AssistCounter>()
+            .init_resource::<GravityAssistTracker>()
+            .add_systems(Update, gravity_assist_check.after(integrate_motion));
+
+        let planet = spawn_mass(&mut app, center, Mass { value: 1e3 * *MASS_OF_SUN });
+        app.world
+            .entity_mut(planet)
+            .insert((Planet, Radius { value: UomLength::new::<meter>(1e9) }, CollisionResponse::Ignore));
+
+        for _ in 0..300 {
+            app.update();
+        }
+
+        let (position, _) = player_motion(&mut app);
+
+        assert!(separation(&position, &center) > 1e11, "the player never left the planet behind");
+        assert_eq!(app.world.resource::<AssistCounter>().count, 1);
+    }
+
+    #[test]
+    fn bounce_planet_reverses_the_player_and_keeps_running() {
+        // Head-on into a massless, perfectly elastic planet, so the normal is along the velocity.
+
+        let speed = UomVelocity::new::<meter_per_second>(1e6);
+
+        let mut app = scene_app(at(1e12, 1e12), Velocity { x: speed, ..Default::default() });
+        app.world.spawn((
+            Planet,
+            at(1e12 + 2e8, 1e12),
+            Radius { value: UomLength::new::<meter>(1e8) },
+            CollisionResponse::Bounce { restitution: 1.0 },
+        ));
+
+        for _ in 0..5 {
+            app.update();
+        }
+
+        let (position, velocity) = player_motion(&mut app);
+
+        assert!(position.x.value < 1e12 + 1e8);
+        assert!((velocity.x + speed).abs().value <= 1e-9 * speed.value);
+        assert_eq!(velocity.y.value, 0.0);
+        assert_eq!(*app.world.resource::<State<GameState>>().get(), GameState::Running);
+    }
+
+    #[test]
+    fn black_hole_pulls_hard_up_to_the_horizon_and_captures() {
+        // A hundred million suns, from a tenth of a Schwarzschild radius outside the horizon; the sideways nudge just
+        // gets the integrator going.
+
+        let mass = Mass { value: 1e8 * *MASS_OF_SUN };
+        let horizon = schwarzschild_radius(mass.value).value;
+        let center = at(3e12, 1.5e12);
+
+        let mut app = scene_app(
+            at(3e12 - 1.1 * horizon, 1.5e12),
+            Velocity {
+                y: UomVelocity::new::<meter_per_second>(1.0),
+                ..Default::default()
+            },
+        );
+        app.world.spawn(BlackHoleBundle {
+            position: center,
+            mass,
+            ..Default::default()
+        });
+
+        let mut previous_speed = 0.0;
+        let mut previous_gain = 0.0;
+
+        for frame in 0..100 {
+            app.update();
+
+            if *app.world.resource::<State<GameState>>().get() != GameState::Running {
+                break;
+            }
+
+            // Each step falls faster than the last, however close to the horizon.
+
+            let (position, velocity) = player_motion(&mut app);
+            let gain = velocity.scalar().value - previous_speed;
+
+            assert!(
+                gain > previous_gain,
+                "frame {}: the pull weakened at {:.4} r_s",
+                frame,
+                separation(&position, &center) / horizon
+            );
+
+            previous_speed = velocity.scalar().value;
+            previous_gain = gain;
+        }
+
+        // Crossing fails the run.
+
+        let (position, _) = player_motion(&mut app);
+
+        assert!(separation(&position, &center) <= horizon);
+        assert_eq!(*app.world.resource::<State<GameState>>().get(), GameState::Paused);
+    }
+}
@@ -0,0 +1,88 @@
+//! Locks in the integrator: a fixed scripted launch on level one, run for a fixed number of physics steps, has to end
+//! where the committed reference says it does.
+//!
+//! The reference is only ever rewritten on purpose: run `UPDATE_GOLDEN=1 cargo test --test physics_golden`, review the
+//! diff to `tests/golden/level_one.ron`, and commit it along with the physics change that moved it. A missing reference
+//! fails the test rather than being recorded, so it can't pass without comparing anything.
+
+use std::{env, fs, path::PathBuf};
+
+use relativity::{game::levels::CurrentLevel, sim::run_headless};
+use serde::{Deserialize, Serialize};
+use uom::si::{length::kilometer, time::day, velocity::kilometer_per_second};
+
+const LAUNCH: (f64, f64) = (0.8, 0.5);
+const FRAMES: usize = 600;
+
+/// Relative tolerance on every value. A run is deterministic on one machine, but floating-point results can differ in
+/// the last few bits across platforms and compiler versions; 1e-9 absorbs that while still flagging any real change to
+/// the physics, which moves the final state by far more.
+const TOLERANCE: f64 = 1e-9;
+
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
+struct GoldenState {
+    position_km: (f64, f64),
+    velocity_km_per_s: (f64, f64),
+    observer_days: f64,
+    player_days: f64,
+    outcome: String,
+}
+
+fn reference_path() -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("tests/golden/level_one.ron")
+}
+
+fn assert_close(name: &str, actual: f64, expected: f64) {
+    assert!(
+        (actual - expected).abs() <= TOLERANCE * actual.abs().max(expected.abs()),
+        "{} moved: {} (reference {}); if intended, rerun with UPDATE_GOLDEN=1",
+        name,
+        actual,
+        expected
+    );
+}
+
+#[test]
+fn level_one_matches_the_golden_run() {
+    let summary = run_headless(CurrentLevel::One, LAUNCH, FRAMES).expect("level one has a player and an observer");
+
+    let actual = GoldenState {
+        position_km: (summary.position.x.get::<kilometer>(), summary.position.y.get::<kilometer>()),
+        velocity_km_per_s: (
+            summary.velocity.x.get::<kilometer_per_second>(),
+            summary.velocity.y.get::<kilometer_per_second>(),
+        ),
+        observer_days: summary.observer_time.get::<day>(),
+        player_days: summary.player_time.get::<day>(),
+        outcome: format!("{:?}", summary.outcome),
+    };
+
+    let path = reference_path();
+
+    if env::var_os("UPDATE_GOLDEN").is_some() {
+        let contents = ron::ser::to_string_pretty(&actual, ron::ser::PrettyConfig::default()).unwrap();
+
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(&path, contents).unwrap();
+
+        println!("recorded the golden run to {}", path.display());
+        return;
+    }
+
+    let Ok(contents) = fs::read_to_string(&path) else {
+        panic!("no golden run at {}; record one with UPDATE_GOLDEN=1", path.display());
+    };
+
+    let expected: GoldenState = ron::from_str(&contents).unwrap();
+
+    assert_eq!(
+        actual.outcome, expected.outcome,
+        "outcome moved; if intended, rerun with UPDATE_GOLDEN=1"
+    );
+    assert_close("position x", actual.position_km.0, expected.position_km.0);
+    assert_close("position y", actual.position_km.1, expected.position_km.1);
+    assert_close("velocity x", actual.velocity_km_per_s.0, expected.velocity_km_per_s.0);
+    assert_close("velocity y", actual.velocity_km_per_s.1, expected.velocity_km_per_s.1);
+    assert_close("observer time", actual.observer_days, expected.observer_days);
+    assert_close("player time", actual.player_days, expected.player_days);
+}